@@ -0,0 +1,120 @@
+//! Implementation of `#[main]`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Ident, ItemFn, ReturnType, Token, Type};
+
+/// The setup steps requested in the attribute's arguments, in the order they are run.
+#[derive(Default)]
+struct Args {
+    stack: bool,
+    log: bool,
+    init: Option<syn::Path>,
+}
+
+enum Arg {
+    Stack,
+    Log,
+    Init(syn::Path),
+}
+
+impl Parse for Arg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+        if key == "stack" {
+            Ok(Arg::Stack)
+        } else if key == "log" {
+            Ok(Arg::Log)
+        } else if key == "init" {
+            input.parse::<Token![=]>()?;
+            Ok(Arg::Init(input.parse()?))
+        } else {
+            Err(syn::Error::new(
+                key.span(),
+                "expected `stack`, `log` or `init = <function>`",
+            ))
+        }
+    }
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Args::default();
+        for arg in Punctuated::<Arg, Token![,]>::parse_terminated(input)? {
+            match arg {
+                Arg::Stack => args.stack = true,
+                Arg::Log => args.log = true,
+                Arg::Init(path) if args.init.is_some() => {
+                    return Err(syn::Error::new_spanned(
+                        path,
+                        "`init` may only be given once",
+                    ));
+                }
+                Arg::Init(path) => args.init = Some(path),
+            }
+        }
+        Ok(args)
+    }
+}
+
+pub(crate) fn main(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let args: Args = syn::parse2(attr)?;
+    let function: ItemFn = syn::parse2(item)?;
+    let sig = &function.sig;
+
+    if !sig.inputs.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &sig.inputs,
+            "the entrypoint cannot take any arguments",
+        ));
+    }
+    if !sig.generics.params.is_empty() || sig.generics.where_clause.is_some() {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            "the entrypoint cannot be generic",
+        ));
+    }
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "the entrypoint cannot be async",
+        ));
+    }
+
+    let ident = &sig.ident;
+    let call = match &sig.output {
+        ReturnType::Default => quote! { #ident(); 0 },
+        ReturnType::Type(_, ty) => match &**ty {
+            Type::Never(_) => quote! { #ident() },
+            Type::Path(path) if path.qself.is_none() && path.path.is_ident("i32") => {
+                quote! { #ident() }
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    "the entrypoint must return `i32`, `!` or nothing",
+                ))
+            }
+        },
+    };
+
+    let stack = args.stack.then(|| quote! { ::mcinterface::stack::init(); });
+    let log = args
+        .log
+        .then(|| quote! { let _ = ::mcinterface::logging::init(); });
+    let init = args.init.map(|path| quote! { #path(); });
+
+    Ok(quote! {
+        #function
+
+        #[no_mangle]
+        pub extern "C" fn _start() -> i32 {
+            #stack
+            #log
+            #init
+            #call
+        }
+    })
+}
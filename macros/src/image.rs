@@ -0,0 +1,68 @@
+//! Implementation of `include_image!`.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use std::path::PathBuf;
+use syn::LitStr;
+
+/// The approximate average colour of the texture of each block, excluding air.
+const PALETTE: [(&str, [u8; 3]); 13] = [
+    ("Cobblestone", [127, 127, 127]),
+    ("Granite", [149, 103, 85]),
+    ("Andesite", [136, 136, 137]),
+    ("Diorite", [188, 188, 189]),
+    ("Lapis", [31, 67, 140]),
+    ("Iron", [220, 220, 220]),
+    ("Gold", [246, 208, 62]),
+    ("Diamond", [98, 237, 228]),
+    ("Redstone", [175, 24, 5]),
+    ("Emerald", [42, 203, 88]),
+    ("Dirt", [134, 96, 67]),
+    ("OakLog", [109, 85, 51]),
+    ("OakLeaves", [60, 96, 30]),
+];
+
+/// Find the name of the `Block` variant closest in colour to a pixel. Mostly transparent pixels
+/// become air.
+fn nearest_block([r, g, b, a]: [u8; 4]) -> &'static str {
+    if a < 128 {
+        return "Air";
+    }
+    PALETTE
+        .iter()
+        .min_by_key(|(_, [pr, pg, pb])| {
+            let dr = r as i32 - *pr as i32;
+            let dg = g as i32 - *pg as i32;
+            let db = b as i32 - *pb as i32;
+            // Weighted to roughly match perceived brightness
+            2 * dr * dr + 4 * dg * dg + 3 * db * db
+        })
+        .map(|(name, _)| *name)
+        .unwrap()
+}
+
+pub(crate) fn include_image(input: TokenStream) -> syn::Result<TokenStream> {
+    let path_lit: LitStr = syn::parse2(input)?;
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = PathBuf::from(manifest_dir).join(path_lit.value());
+    let image = image::open(&path)
+        .map_err(|e| {
+            syn::Error::new(
+                path_lit.span(),
+                format!("failed to load {}: {e}", path.display()),
+            )
+        })?
+        .to_rgba8();
+
+    let rows = image.rows().map(|row| {
+        let blocks = row.map(|pixel| Ident::new(nearest_block(pixel.0), Span::call_site()));
+        quote! { [ #( ::mcinterface::Block::#blocks ),* ] }
+    });
+    let path = path.to_string_lossy();
+
+    Ok(quote! {{
+        // Make sure the crate is rebuilt when the image changes
+        const _: &[u8] = include_bytes!(#path);
+        ::mcinterface::sprite::Sprite([ #( #rows ),* ])
+    }})
+}
@@ -0,0 +1,57 @@
+//! Procedural macros for [mcinterface](https://docs.rs/mcinterface).
+//!
+//! These macros are re-exported by mcinterface when the corresponding features are enabled, and
+//! should be used through it rather than by depending on this crate directly.
+
+#[cfg(feature = "main")]
+mod entry;
+#[cfg(feature = "image")]
+mod image;
+#[cfg(feature = "testing")]
+mod mc_test;
+#[cfg(feature = "schematic")]
+mod schematic;
+
+/// Embed an image file as a [`Sprite`](https://docs.rs/mcinterface/latest/mcinterface/sprite/struct.Sprite.html)
+/// at compile time. See the documentation in mcinterface for details.
+#[cfg(feature = "image")]
+#[proc_macro]
+pub fn include_image(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    image::include_image(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Embed a structure file as a [`Schematic`](https://docs.rs/mcinterface/latest/mcinterface/schematic/struct.Schematic.html)
+/// at compile time. See the documentation in mcinterface for details.
+#[cfg(feature = "schematic")]
+#[proc_macro]
+pub fn include_schematic(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    schematic::include_schematic(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Turn a function into the wasmcraft2 entrypoint. See the documentation in mcinterface for details.
+#[cfg(feature = "main")]
+#[proc_macro_attribute]
+pub fn main(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    entry::main(attr.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Mark a function as a test to be run in-game. See the documentation in mcinterface for details.
+#[cfg(feature = "testing")]
+#[proc_macro_attribute]
+pub fn mc_test(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    mc_test::mc_test(attr.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,63 @@
+//! Implementation of `#[mc_test]`.
+
+use proc_macro2::TokenStream;
+use quote::quote_spanned;
+use syn::{Ident, ItemFn, ReturnType};
+
+pub(crate) fn mc_test(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let should_panic = if attr.is_empty() {
+        false
+    } else {
+        let key: Ident = syn::parse2(attr)?;
+        if key != "should_panic" {
+            return Err(syn::Error::new(key.span(), "expected `should_panic`"));
+        }
+        true
+    };
+
+    let ItemFn {
+        attrs,
+        vis: _,
+        sig,
+        block,
+    } = syn::parse2(item)?;
+    if !sig.inputs.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &sig.inputs,
+            "tests cannot take any arguments",
+        ));
+    }
+    if !sig.generics.params.is_empty() || sig.generics.where_clause.is_some() {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            "tests cannot be generic",
+        ));
+    }
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new_spanned(asyncness, "tests cannot be async"));
+    }
+    if let ReturnType::Type(_, ty) = &sig.output {
+        return Err(syn::Error::new_spanned(ty, "tests cannot return a value"));
+    }
+
+    // The test is always visible to the whole crate, so that `mc_test_main!` can list it from
+    // anywhere, and never outside it, so that a test that is not listed is reported as unused. The
+    // span of the function name is used, since lints are not reported for items that come entirely
+    // from a macro.
+    let ident = &sig.ident;
+    let test = quote_spanned! {ident.span()=>
+        #[allow(non_upper_case_globals)]
+        #[deny(dead_code)]
+        pub(crate) const #ident: ::mcinterface::testing::TestCase = {
+            #(#attrs)*
+            #sig #block
+
+            ::mcinterface::testing::TestCase {
+                name: ::core::concat!(::core::module_path!(), "::", ::core::stringify!(#ident)),
+                run: #ident,
+                should_panic: #should_panic,
+            }
+        };
+    };
+    Ok(test)
+}
@@ -0,0 +1,370 @@
+//! Implementation of `include_schematic!`.
+
+use fastnbt::Value;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use syn::parse::{Parse, ParseStream};
+use syn::{LitStr, Token};
+
+/// The `Block` variants, for validating the fallback block.
+const BLOCKS: [&str; 14] = [
+    "Air",
+    "Cobblestone",
+    "Granite",
+    "Andesite",
+    "Diorite",
+    "Lapis",
+    "Iron",
+    "Gold",
+    "Diamond",
+    "Redstone",
+    "Emerald",
+    "Dirt",
+    "OakLog",
+    "OakLeaves",
+];
+
+/// Map a Minecraft block state (e.g. `minecraft:oak_log[axis=y]`) to the name of a `Block` variant.
+fn block_variant(state: &str) -> Option<&'static str> {
+    let name = state.split('[').next().unwrap_or(state);
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    Some(match name {
+        "air" | "cave_air" | "void_air" | "structure_void" => "Air",
+        "cobblestone" => "Cobblestone",
+        "granite" => "Granite",
+        "andesite" => "Andesite",
+        "diorite" => "Diorite",
+        "lapis_block" => "Lapis",
+        "iron_block" => "Iron",
+        "gold_block" => "Gold",
+        "diamond_block" => "Diamond",
+        "redstone_block" => "Redstone",
+        "emerald_block" => "Emerald",
+        "dirt" => "Dirt",
+        "oak_log" => "OakLog",
+        "oak_leaves" => "OakLeaves",
+        _ => return None,
+    })
+}
+
+struct Input {
+    path: LitStr,
+    fallback: Option<Ident>,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        let mut fallback = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key != "fallback" {
+                return Err(syn::Error::new(key.span(), "expected `fallback = <block>`"));
+            }
+            input.parse::<Token![=]>()?;
+            let block: syn::Path = input.parse()?;
+            let ident = block.segments.last().unwrap().ident.clone();
+            if !BLOCKS.iter().any(|b| ident == b) {
+                return Err(syn::Error::new(ident.span(), "unknown block"));
+            }
+            fallback = Some(ident);
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(Self { path, fallback })
+    }
+}
+
+/// A structure loaded from a file, with blocks stored as palette indices in YZX order.
+struct Structure {
+    size: [usize; 3],
+    palette: Vec<String>,
+    blocks: Vec<usize>,
+}
+
+fn get<'a>(value: &'a Value, key: &str) -> Result<&'a Value, String> {
+    match value {
+        Value::Compound(map) => map.get(key).ok_or_else(|| format!("missing tag `{key}`")),
+        _ => Err(format!("expected a compound containing `{key}`")),
+    }
+}
+
+fn get_int(value: &Value, key: &str) -> Result<i64, String> {
+    match get(value, key)? {
+        Value::Byte(n) => Ok(*n as i64),
+        Value::Short(n) => Ok(*n as i64),
+        Value::Int(n) => Ok(*n as i64),
+        Value::Long(n) => Ok(*n),
+        _ => Err(format!("expected `{key}` to be an integer")),
+    }
+}
+
+fn get_list<'a>(value: &'a Value, key: &str) -> Result<&'a [Value], String> {
+    match get(value, key)? {
+        Value::List(list) => Ok(list),
+        _ => Err(format!("expected `{key}` to be a list")),
+    }
+}
+
+fn get_string<'a>(value: &'a Value, key: &str) -> Result<&'a str, String> {
+    match get(value, key)? {
+        Value::String(s) => Ok(s),
+        _ => Err(format!("expected `{key}` to be a string")),
+    }
+}
+
+/// Load a Sponge schematic (`.schem`), versions 1 to 3.
+fn load_sponge(root: &Value) -> Result<Structure, String> {
+    // Version 3 wraps everything in a `Schematic` compound and moves the palette into `Blocks`
+    let root = get(root, "Schematic").unwrap_or(root);
+    let blocks = get(root, "Blocks").unwrap_or(root);
+    let size = [
+        get_int(root, "Width")? as u16 as usize,
+        get_int(root, "Height")? as u16 as usize,
+        get_int(root, "Length")? as u16 as usize,
+    ];
+
+    let Value::Compound(palette_map) = get(blocks, "Palette")? else {
+        return Err("expected `Palette` to be a compound".into());
+    };
+    let mut palette = vec![String::new(); palette_map.len()];
+    for (name, index) in palette_map {
+        let Value::Int(index) = index else {
+            return Err("expected palette indices to be integers".into());
+        };
+        *palette
+            .get_mut(*index as usize)
+            .ok_or("palette index out of range")? = name.clone();
+    }
+
+    let data = match get(blocks, "BlockData").or_else(|_| get(blocks, "Data"))? {
+        Value::ByteArray(data) => data,
+        _ => return Err("expected block data to be a byte array".into()),
+    };
+    let mut indices = Vec::with_capacity(size.iter().product());
+    let (mut value, mut shift) = (0usize, 0);
+    for byte in data.iter().map(|b| *b as u8) {
+        value |= ((byte & 0x7f) as usize) << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            indices.push(value);
+            value = 0;
+            shift = 0;
+        }
+    }
+
+    Ok(Structure {
+        size,
+        palette,
+        blocks: indices,
+    })
+}
+
+/// Load a vanilla structure block file (`.nbt`).
+fn load_structure(root: &Value) -> Result<Structure, String> {
+    let size = get_list(root, "size")?
+        .iter()
+        .map(|v| match v {
+            Value::Int(n) => Ok(*n as usize),
+            _ => Err("expected `size` to contain integers".to_string()),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let size: [usize; 3] = size
+        .try_into()
+        .map_err(|_| "expected `size` to have 3 elements")?;
+
+    let mut palette = get_list(root, "palette")?
+        .iter()
+        .map(|state| get_string(state, "Name").map(str::to_string))
+        .collect::<Result<Vec<_>, _>>()?;
+    // Positions not present in the file are structure voids, which are placed as air
+    let air = palette.len();
+    palette.push("minecraft:air".into());
+
+    let mut blocks = vec![air; size.iter().product()];
+    for block in get_list(root, "blocks")? {
+        let pos = get_list(block, "pos")?;
+        let [Value::Int(x), Value::Int(y), Value::Int(z)] = pos else {
+            return Err("expected `pos` to contain 3 integers".into());
+        };
+        let index = (*y as usize * size[2] + *z as usize) * size[0] + *x as usize;
+        *blocks.get_mut(index).ok_or("block position out of range")? =
+            get_int(block, "state")? as usize;
+    }
+
+    Ok(Structure {
+        size,
+        palette,
+        blocks,
+    })
+}
+
+/// Load a Litematica schematic (`.litematic`), merging all of its regions.
+fn load_litematic(root: &Value) -> Result<Structure, String> {
+    let Value::Compound(regions) = get(root, "Regions")? else {
+        return Err("expected `Regions` to be a compound".into());
+    };
+
+    // Regions may have negative sizes, in which case they extend backwards from their position
+    let bounds = |region: &Value| -> Result<([i64; 3], [usize; 3]), String> {
+        let position = get(region, "Position")?;
+        let size = get(region, "Size")?;
+        let mut min = [0; 3];
+        let mut extent = [0; 3];
+        for (axis, key) in ["x", "y", "z"].iter().enumerate() {
+            let p = get_int(position, key)?;
+            let s = get_int(size, key)?;
+            min[axis] = if s < 0 { p + s + 1 } else { p };
+            extent[axis] = s.unsigned_abs() as usize;
+        }
+        Ok((min, extent))
+    };
+
+    let mut total_min = [i64::MAX; 3];
+    let mut total_max = [i64::MIN; 3];
+    for region in regions.values() {
+        let (min, extent) = bounds(region)?;
+        for axis in 0..3 {
+            total_min[axis] = total_min[axis].min(min[axis]);
+            total_max[axis] = total_max[axis].max(min[axis] + extent[axis] as i64);
+        }
+    }
+    if regions.is_empty() {
+        total_min = [0; 3];
+        total_max = [0; 3];
+    }
+    let size = [0, 1, 2].map(|axis| (total_max[axis] - total_min[axis]) as usize);
+
+    let mut palette = vec!["minecraft:air".to_string()];
+    let mut palette_indices = HashMap::new();
+    let mut blocks = vec![0; size.iter().product()];
+    for region in regions.values() {
+        let (min, extent) = bounds(region)?;
+        let region_palette = get_list(region, "BlockStatePalette")?
+            .iter()
+            .map(|state| {
+                let name = get_string(state, "Name")?;
+                Ok(*palette_indices.entry(name.to_string()).or_insert_with(|| {
+                    palette.push(name.to_string());
+                    palette.len() - 1
+                }))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let Value::LongArray(states) = get(region, "BlockStates")? else {
+            return Err("expected `BlockStates` to be a long array".into());
+        };
+
+        let bits =
+            (usize::BITS - (region_palette.len().max(1) - 1).leading_zeros()).max(2) as usize;
+        let mask = (1u64 << bits) - 1;
+        for y in 0..extent[1] {
+            for z in 0..extent[2] {
+                for x in 0..extent[0] {
+                    let bit = ((y * extent[2] + z) * extent[0] + x) * bits;
+                    let (word, offset) = (bit / 64, bit % 64);
+                    let mut value =
+                        *states.get(word).ok_or("block states out of range")? as u64 >> offset;
+                    if offset + bits > 64 {
+                        let next = *states.get(word + 1).ok_or("block states out of range")? as u64;
+                        value |= next << (64 - offset);
+                    }
+                    let state = *region_palette
+                        .get((value & mask) as usize)
+                        .ok_or("palette index out of range")?;
+                    let pos = [x, y, z].map(|n| n as i64);
+                    let [wx, wy, wz] =
+                        [0, 1, 2].map(|axis| (pos[axis] + min[axis] - total_min[axis]) as usize);
+                    blocks[(wy * size[2] + wz) * size[0] + wx] = state;
+                }
+            }
+        }
+    }
+
+    Ok(Structure {
+        size,
+        palette,
+        blocks,
+    })
+}
+
+fn load(path: &Path) -> Result<Structure, String> {
+    let mut data = std::fs::read(path).map_err(|e| e.to_string())?;
+    if data.starts_with(&[0x1f, 0x8b]) {
+        let mut decompressed = Vec::new();
+        flate2::read::GzDecoder::new(&data[..])
+            .read_to_end(&mut decompressed)
+            .map_err(|e| e.to_string())?;
+        data = decompressed;
+    }
+    let root: Value = fastnbt::from_bytes(&data).map_err(|e| e.to_string())?;
+
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("schem") => load_sponge(&root),
+        Some("nbt") => load_structure(&root),
+        Some("litematic") => load_litematic(&root),
+        _ => Err("unsupported file type, expected .schem, .nbt or .litematic".into()),
+    }
+}
+
+pub(crate) fn include_schematic(input: TokenStream) -> syn::Result<TokenStream> {
+    let Input {
+        path: path_lit,
+        fallback,
+    } = syn::parse2(input)?;
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = PathBuf::from(manifest_dir).join(path_lit.value());
+    let error = |msg: String| {
+        syn::Error::new(
+            path_lit.span(),
+            format!("failed to load {}: {msg}", path.display()),
+        )
+    };
+    let structure = load(&path).map_err(error)?;
+
+    let mut palette = Vec::with_capacity(structure.palette.len());
+    let mut unknown = Vec::new();
+    for state in &structure.palette {
+        match (block_variant(state), &fallback) {
+            (Some(variant), _) => palette.push(Ident::new(variant, Span::call_site())),
+            (None, Some(fallback)) => palette.push(fallback.clone()),
+            (None, None) => {
+                unknown.push(state.as_str());
+                palette.push(Ident::new("Air", Span::call_site()));
+            }
+        }
+    }
+    if !unknown.is_empty() {
+        return Err(syn::Error::new(
+            path_lit.span(),
+            format!(
+                "blocks not supported by wasmcraft2: {} (add `fallback = <block>` to replace them)",
+                unknown.join(", "),
+            ),
+        ));
+    }
+
+    let blocks = structure
+        .blocks
+        .iter()
+        .map(|index| {
+            palette
+                .get(*index)
+                .ok_or_else(|| error("palette index out of range".into()))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let [width, height, length] = structure.size;
+    let path = path.to_string_lossy();
+
+    Ok(quote! {{
+        // Make sure the crate is rebuilt when the schematic changes
+        const _: &[u8] = include_bytes!(#path);
+        ::mcinterface::schematic::Schematic {
+            width: #width,
+            height: #height,
+            length: #length,
+            blocks: &[ #( ::mcinterface::Block::#blocks ),* ],
+        }
+    }})
+}
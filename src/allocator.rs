@@ -0,0 +1,146 @@
+//! A simple global allocator.
+//!
+//! With the `alloc` feature enabled, this crate registers a [`BumpAllocator`] as the global
+//! allocator, which allows the [`alloc`](https://doc.rust-lang.org/alloc/) crate (`Vec`, `String`,
+//! `format!` and so on) to be used:
+//! ```ignore
+//! extern crate alloc;
+//! use alloc::vec::Vec;
+//! ```
+//!
+//! The allocator hands out memory from a fixed-size static arena, which is [`ARENA_SIZE`] bytes
+//! (16 KiB by default). This can be changed by setting the `MCINTERFACE_ARENA_SIZE` environment
+//! variable at compile time, for example in your `.cargo/config`:
+//! ```toml
+//! [env]
+//! MCINTERFACE_ARENA_SIZE = "65536"
+//! ```
+//!
+//! Memory is only reclaimed when the most recent allocation is freed, or when every allocation has
+//! been freed, so programs that keep some allocations alive for a long time while repeatedly
+//! allocating and freeing others may run out of memory. Running out of memory causes a panic.
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::{Cell, UnsafeCell};
+
+/// Parse a decimal number at compile time, or return `default` if it is not a valid number.
+const fn parse_usize(s: Option<&str>, default: usize) -> usize {
+    let bytes = match s {
+        Some(s) if !s.is_empty() => s.as_bytes(),
+        _ => return default,
+    };
+    let mut value: usize = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            return default;
+        }
+        value = match value.checked_mul(10) {
+            Some(v) => match v.checked_add((bytes[i] - b'0') as usize) {
+                Some(v) => v,
+                None => return default,
+            },
+            None => return default,
+        };
+        i += 1;
+    }
+    value
+}
+
+/// The size of the global allocator's arena in bytes, set by the `MCINTERFACE_ARENA_SIZE`
+/// environment variable at compile time.
+pub const ARENA_SIZE: usize = parse_usize(option_env!("MCINTERFACE_ARENA_SIZE"), 16384);
+
+/// A bump allocator over a static arena of `SIZE` bytes.
+///
+/// Allocations are made by moving a pointer forwards through the arena. Freeing the most recent
+/// allocation moves the pointer back, and freeing every allocation resets the arena. Reallocating the
+/// most recent allocation grows or shrinks it in place.
+///
+/// This allocator is not thread-safe, which is fine since wasmcraft2 programs are single-threaded.
+pub struct BumpAllocator<const SIZE: usize> {
+    arena: UnsafeCell<[u8; SIZE]>,
+    next: Cell<usize>,
+    live: Cell<usize>,
+}
+
+// SAFETY: wasmcraft2 programs are single-threaded
+unsafe impl<const SIZE: usize> Sync for BumpAllocator<SIZE> {}
+
+impl<const SIZE: usize> BumpAllocator<SIZE> {
+    /// Create a new allocator with an empty arena.
+    pub const fn new() -> Self {
+        Self {
+            arena: UnsafeCell::new([0; SIZE]),
+            next: Cell::new(0),
+            live: Cell::new(0),
+        }
+    }
+
+    /// The number of bytes of the arena currently in use, including alignment padding.
+    #[inline(always)]
+    pub fn used(&self) -> usize {
+        self.next.get()
+    }
+
+    /// The number of bytes of the arena available for new allocations.
+    #[inline(always)]
+    pub fn remaining(&self) -> usize {
+        SIZE - self.next.get()
+    }
+
+    #[inline(always)]
+    fn base(&self) -> *mut u8 {
+        self.arena.get() as *mut u8
+    }
+}
+
+impl<const SIZE: usize> Default for BumpAllocator<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl<const SIZE: usize> GlobalAlloc for BumpAllocator<SIZE> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let base = self.base() as usize;
+        let start = (base + self.next.get()).next_multiple_of(layout.align()) - base;
+        match start.checked_add(layout.size()) {
+            Some(end) if end <= SIZE => {
+                self.next.set(end);
+                self.live.set(self.live.get() + 1);
+                self.base().add(start)
+            }
+            _ => core::ptr::null_mut(),
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.live.set(self.live.get() - 1);
+        let offset = ptr as usize - self.base() as usize;
+        if self.live.get() == 0 {
+            self.next.set(0);
+        } else if offset + layout.size() == self.next.get() {
+            self.next.set(offset);
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let offset = ptr as usize - self.base() as usize;
+        if offset + layout.size() == self.next.get() && offset + new_size <= SIZE {
+            self.next.set(offset + new_size);
+            return ptr;
+        }
+        let new_ptr = self.alloc(Layout::from_size_align_unchecked(new_size, layout.align()));
+        if !new_ptr.is_null() {
+            core::ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+            self.dealloc(ptr, layout);
+        }
+        new_ptr
+    }
+}
+
+/// The global allocator.
+#[cfg(not(any(test, feature = "simulator")))]
+#[global_allocator]
+pub static ALLOCATOR: BumpAllocator<ARENA_SIZE> = BumpAllocator::new();
@@ -0,0 +1,108 @@
+//! Frame-by-frame animations.
+//!
+//! Contains [`Animation`], which plays a sequence of [`Frame`]s at the turtle position, waiting a
+//! given number of game ticks after each frame.
+
+use crate::{mc_sleep, turtle_pos, turtle_position, turtle_set, turtle_x, turtle_y, Block};
+
+/// A single frame of an [`Animation`], together with the number of game ticks to wait after drawing
+/// it.
+pub enum Frame<'a, const W: usize, const H: usize> {
+    /// A `W` by `H` block array, stored as rows from top to bottom, drawn on a vertical wall in the
+    /// XY plane with its top left corner at the turtle position (the same layout as a
+    /// [`Sprite`](crate::sprite::Sprite)).
+    Blocks(&'a [[Block; W]; H], u32),
+    /// A function which draws the frame. The turtle is at the animation's position when it is called,
+    /// and is returned there afterwards.
+    Draw(&'a dyn Fn(), u32),
+}
+
+impl<const W: usize, const H: usize> Frame<'_, W, H> {
+    /// The number of game ticks to wait after drawing this frame.
+    #[inline(always)]
+    pub fn ticks(&self) -> u32 {
+        match self {
+            Frame::Blocks(_, ticks) | Frame::Draw(_, ticks) => *ticks,
+        }
+    }
+}
+
+/// An animation made of a sequence of [`Frame`]s.
+///
+/// When skipping unchanged cells is enabled (the default), a [`Frame::Blocks`] frame that directly
+/// follows another [`Frame::Blocks`] frame only places the blocks that differ between the two, which
+/// greatly reduces the number of commands needed for animations where little changes between frames.
+/// This assumes nothing else modifies the animation's area while it is playing.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{Block, animation::{Animation, Frame}, turtle_pos};
+/// const ON: [[Block; 2]; 2] = [[Block::Redstone; 2]; 2];
+/// const OFF: [[Block; 2]; 2] = [[Block::Cobblestone; 2]; 2];
+/// let frames = [Frame::Blocks(&ON, 10), Frame::Blocks(&OFF, 10)];
+/// turtle_pos(0, 100, 0);
+/// Animation::new(&frames).looping(true).play();
+/// ```
+pub struct Animation<'a, const W: usize, const H: usize> {
+    frames: &'a [Frame<'a, W, H>],
+    looping: bool,
+    skip_unchanged: bool,
+}
+
+impl<'a, const W: usize, const H: usize> Animation<'a, W, H> {
+    /// Create a new animation that plays the given frames once, skipping unchanged cells.
+    pub const fn new(frames: &'a [Frame<'a, W, H>]) -> Self {
+        Self { frames, looping: false, skip_unchanged: true }
+    }
+
+    /// Set whether the animation restarts from the first frame after the last frame.
+    pub const fn looping(self, looping: bool) -> Self {
+        Self { looping, ..self }
+    }
+
+    /// Set whether consecutive [`Frame::Blocks`] frames only place the blocks that have changed.
+    pub const fn skip_unchanged(self, skip_unchanged: bool) -> Self {
+        Self { skip_unchanged, ..self }
+    }
+
+    /// Play the animation at the turtle position. If the animation is looping, this never returns.
+    ///
+    /// The turtle is returned to its starting position after each frame.
+    pub fn play(&self) {
+        let (x, y, z) = turtle_position();
+        let mut previous: Option<&[[Block; W]; H]> = None;
+        loop {
+            for frame in self.frames {
+                match frame {
+                    Frame::Blocks(blocks, _) => {
+                        let previous = previous.replace(blocks).filter(|_| self.skip_unchanged);
+                        for (row_index, row) in blocks.iter().enumerate() {
+                            let previous_row = previous.map(|p| &p[row_index]);
+                            if previous_row == Some(row) {
+                                continue;
+                            }
+                            turtle_y(y - row_index as i32);
+                            for (column, block) in row.iter().enumerate() {
+                                if previous_row.map(|p| p[column]) != Some(*block) {
+                                    turtle_x(x + column as i32);
+                                    turtle_set(*block);
+                                }
+                            }
+                        }
+                    }
+                    Frame::Draw(draw, _) => {
+                        previous = None;
+                        draw();
+                    }
+                }
+                turtle_pos(x, y, z);
+                for _ in 0..frame.ticks() {
+                    mc_sleep();
+                }
+            }
+            if !self.looping || self.frames.is_empty() {
+                return;
+            }
+        }
+    }
+}
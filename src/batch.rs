@@ -0,0 +1,157 @@
+//! Batching block placements.
+//!
+//! Every turtle function is a separate call into wasmcraft2, so placing scattered blocks with
+//! [`turtle_pos`](crate::turtle_pos) and [`turtle_set`] costs four calls per block. [`BatchBuilder`]
+//! records writes instead of making them immediately, and when it is committed, places them in an
+//! order that changes as few turtle coordinates as possible, using a single [`turtle_fill`] for each
+//! run of identical blocks along the x axis.
+
+use crate::collections::FixedVec;
+use crate::vec::IVec3;
+use crate::{turtle_fill, turtle_position, turtle_set, turtle_x, turtle_y, turtle_z, Block};
+
+/// A pending write, with its position in the order the writes were made so that later writes to
+/// the same position win.
+#[derive(Copy, Clone)]
+struct Write {
+    pos: IVec3,
+    block: Block,
+    seq: u32,
+}
+
+/// Records up to `N` block writes and places them together.
+///
+/// Writes are placed in order of y, then z, then x, so that the turtle moves through the batch
+/// layer by layer and row by row. Consecutive identical blocks along the x axis are placed with one
+/// [`turtle_fill`], unless this is turned off with [`set_use_fill`](BatchBuilder::set_use_fill)
+/// (since `turtle_fill` may cause wasmcraft2 to fail compilation). If the same position is written
+/// more than once, the last write wins.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{batch::BatchBuilder, Block};
+/// let mut batch: BatchBuilder<256> = BatchBuilder::new();
+/// for x in 0..16 {
+///     batch.set((x, 64, x % 4), Block::Gold);
+///     batch.set((x, 64, 0), Block::Cobblestone);
+/// }
+/// batch.commit();
+/// ```
+pub struct BatchBuilder<const N: usize> {
+    writes: FixedVec<Write, N>,
+    seq: u32,
+    use_fill: bool,
+}
+
+impl<const N: usize> BatchBuilder<N> {
+    /// Create an empty batch.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self { writes: FixedVec::new(), seq: 0, use_fill: true }
+    }
+
+    /// Set whether runs of identical blocks are placed with [`turtle_fill`] (the default) rather
+    /// than one [`turtle_set`] per block.
+    #[inline(always)]
+    pub fn set_use_fill(&mut self, use_fill: bool) {
+        self.use_fill = use_fill;
+    }
+
+    /// The number of writes waiting to be committed.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.writes.len()
+    }
+
+    /// Check whether there are no writes waiting to be committed.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    /// Record a block to be placed at a position. If the batch is already full, it is committed
+    /// first.
+    pub fn set(&mut self, pos: impl Into<IVec3>, block: Block) {
+        if self.writes.is_full() {
+            self.commit();
+        }
+        let write = Write { pos: pos.into(), block, seq: self.seq };
+        self.seq = self.seq.wrapping_add(1);
+        let _ = self.writes.push(write);
+    }
+
+    /// Discard every pending write without placing it.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.writes.clear();
+        self.seq = 0;
+    }
+
+    /// Place every pending write and empty the batch. Returns the number of blocks placed, not
+    /// counting writes that were overwritten by later writes to the same position.
+    ///
+    /// This moves the turtle.
+    pub fn commit(&mut self) -> usize {
+        let writes = self.writes.as_mut_slice();
+        writes.sort_unstable_by_key(|w| (w.pos.y, w.pos.z, w.pos.x, w.seq));
+
+        let (mut x, mut y, mut z) = turtle_position();
+        let mut placed = 0;
+        let mut i = 0;
+        while i < writes.len() {
+            // Skip to the last write to this position
+            let mut write = writes[i];
+            while i + 1 < writes.len() && writes[i + 1].pos == write.pos {
+                i += 1;
+                write = writes[i];
+            }
+            i += 1;
+
+            // Extend the run while the next position along x gets the same block
+            let mut run = 0;
+            if self.use_fill {
+                while let Some(next) = writes.get(i) {
+                    let mut last = *next;
+                    let mut j = i;
+                    while j + 1 < writes.len() && writes[j + 1].pos == last.pos {
+                        j += 1;
+                        last = writes[j];
+                    }
+                    if last.pos != write.pos + IVec3::X * (run + 1) || last.block != write.block {
+                        break;
+                    }
+                    run += 1;
+                    i = j + 1;
+                }
+            }
+
+            if write.pos.y != y {
+                y = write.pos.y;
+                turtle_y(y);
+            }
+            if write.pos.z != z {
+                z = write.pos.z;
+                turtle_z(z);
+            }
+            if write.pos.x != x {
+                x = write.pos.x;
+                turtle_x(x);
+            }
+            if run == 0 {
+                turtle_set(write.block);
+            } else {
+                turtle_fill(write.block, run, 0, 0);
+            }
+            placed += run as usize + 1;
+        }
+
+        self.clear();
+        placed
+    }
+}
+
+impl<const N: usize> Default for BatchBuilder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
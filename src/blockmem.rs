@@ -0,0 +1,119 @@
+//! Storing data in the world.
+//!
+//! WASM memory is small and is lost whenever the program restarts, but the world is large and
+//! persistent. [`BlockMemory`] stores arrays of `i32` values in blocks, with each value encoded in
+//! base 14 as a vertical column of [`DIGITS_PER_VALUE`] blocks (one digit per [`Block`] variant).
+//!
+//! [`Block::Air`] represents the digit 0, so storage that has never been written reads as zeroes.
+
+use crate::region::Region;
+use crate::{turtle_get, turtle_set, turtle_x, turtle_y, turtle_z, Block};
+
+/// The number of distinct blocks, and so the base used to encode values.
+const BASE: u32 = Block::ALL.len() as u32;
+
+/// The number of blocks used to store a single value.
+pub const DIGITS_PER_VALUE: usize = 9;
+
+/// An array of `i32` values stored in the world.
+///
+/// Values are stored in vertical columns of [`DIGITS_PER_VALUE`] blocks, starting from the origin
+/// and going up, with the least significant digit at the bottom. Value `i` is stored in the column at
+/// `x = origin.x + i % width` and `z = origin.z + i / width`, so the storage is a `width` blocks wide
+/// slab which grows towards positive z as more values are stored.
+///
+/// Nothing prevents the storage area from being modified by players or other code, so it should be
+/// placed somewhere out of the way.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::blockmem::BlockMemory;
+/// let memory = BlockMemory::new((0, 0, 0), 16);
+/// memory.write_array(0, &[1, 2, 3]);
+/// let mut values = [0; 3];
+/// memory.read_array(0, &mut values);
+/// ```
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct BlockMemory {
+    origin: (i32, i32, i32),
+    width: usize,
+}
+
+impl BlockMemory {
+    /// Create storage starting at the given origin, storing `width` values along the x axis before
+    /// moving to the next row. `width` must be at least 1.
+    #[inline(always)]
+    pub const fn new(origin: (i32, i32, i32), width: usize) -> Self {
+        Self { origin, width }
+    }
+
+    /// The minimum corner of the storage area.
+    #[inline(always)]
+    pub const fn origin(&self) -> (i32, i32, i32) {
+        self.origin
+    }
+
+    /// Get the region of the world used to store the first `len` values. `len` must be at least 1.
+    pub fn region(&self, len: usize) -> Region {
+        let rows = (len - 1) / self.width + 1;
+        let columns = if rows > 1 { self.width } else { len };
+        Region::from_size(
+            self.origin,
+            (columns as i32, DIGITS_PER_VALUE as i32, rows as i32),
+        )
+    }
+
+    /// Move the turtle to the bottom of the column storing the value at `index`.
+    #[inline(always)]
+    fn seek(&self, index: usize) {
+        turtle_x(self.origin.0 + (index % self.width) as i32);
+        turtle_z(self.origin.2 + (index / self.width) as i32);
+    }
+
+    /// Store a value at the given index.
+    ///
+    /// This moves the turtle, so the turtle position is undefined after calling this function.
+    pub fn write(&self, index: usize, value: i32) {
+        self.seek(index);
+        let mut value = value as u32;
+        for digit in 0..DIGITS_PER_VALUE {
+            turtle_y(self.origin.1 + digit as i32);
+            turtle_set(Block::ALL[(value % BASE) as usize]);
+            value /= BASE;
+        }
+    }
+
+    /// Read the value at the given index.
+    ///
+    /// The result is only meaningful if the column was written by [`BlockMemory::write`], or has never
+    /// been written (in which case it is 0).
+    ///
+    /// This moves the turtle, so the turtle position is undefined after calling this function.
+    pub fn read(&self, index: usize) -> i32 {
+        self.seek(index);
+        let mut value: u32 = 0;
+        for digit in (0..DIGITS_PER_VALUE).rev() {
+            turtle_y(self.origin.1 + digit as i32);
+            value = value.wrapping_mul(BASE).wrapping_add(turtle_get().as_i32() as u32);
+        }
+        value as i32
+    }
+
+    /// Store an array of values, starting at the given index.
+    ///
+    /// This moves the turtle, so the turtle position is undefined after calling this function.
+    pub fn write_array(&self, start: usize, values: &[i32]) {
+        for (i, value) in values.iter().enumerate() {
+            self.write(start + i, *value);
+        }
+    }
+
+    /// Read values into an array, starting at the given index.
+    ///
+    /// This moves the turtle, so the turtle position is undefined after calling this function.
+    pub fn read_array(&self, start: usize, values: &mut [i32]) {
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = self.read(start + i);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Plotting integer data as columns of blocks.
+//!
+//! [`render_heightmap`] builds a column of blocks for each `(x, z)` position of a region, and
+//! [`render_bar_chart`] builds a bar for each value, scaled to the height of a region. Each column
+//! is placed with at most two [`turtle_fill`]s (one for the blocks and one for the air above them),
+//! so the whole region is redrawn without placing blocks one at a time.
+//!
+//! When redrawing data that changes a little each frame, [`update_heightmap`] and
+//! [`update_bar_chart`] take the previous values as well, and only fill the part of each column that
+//! changed, which needs at most one [`turtle_fill`] per changed column.
+//!
+//! [`turtle_fill`] may cause wasmcraft2 to fail compilation, so these functions should not be used
+//! with builds of wasmcraft2 where that is the case.
+//!
+//! Usage:
+//! ```ignore
+//! # use mcinterface::{Block, chart::{render_bar_chart, update_bar_chart}, region::Region};
+//! let region = Region::from_size((0, 100, 0), (16, 10, 1));
+//! let mut readings = [0; 8];
+//! render_bar_chart(&readings, 100, &region, Block::Gold);
+//! loop {
+//!     let previous = readings;
+//!     // ... update readings ...
+//!     update_bar_chart(&readings, &previous, 100, &region, Block::Gold);
+//! }
+//! ```
+
+use crate::region::Region;
+use crate::{turtle_fill, turtle_pos, Block};
+
+/// Fill the blocks of a column (a region covering one heightmap position or bar) from height `from`
+/// up to but not including height `to`.
+fn fill_column(column: &Region, from: i32, to: i32, block: Block) {
+    if from < to {
+        let (x, y, z) = column.min();
+        let (size_x, _, size_z) = column.size();
+        turtle_pos(x, y + from, z);
+        turtle_fill(block, size_x - 1, to - from - 1, size_z - 1);
+    }
+}
+
+/// Draw a column `height` blocks tall with the rest of the column above it filled with air. If
+/// `previous` is `Some`, only the difference from a column of that height is drawn.
+fn draw_column(column: &Region, height: i32, previous: Option<i32>, block: Block) {
+    let total = column.size().1;
+    match previous {
+        Some(previous) if height >= previous => fill_column(column, previous, height, block),
+        Some(previous) => fill_column(column, height, previous, Block::Air),
+        None => {
+            fill_column(column, 0, height, block);
+            fill_column(column, height, total, Block::Air);
+        }
+    }
+}
+
+/// Scale a bar chart value to a height out of `total` blocks, with `max` filling the whole column.
+fn bar_height(value: i32, max: i32, total: i32) -> i32 {
+    let max = max.max(1);
+    (value.clamp(0, max) as i64 * total as i64 / max as i64) as i32
+}
+
+/// Visit the `(x, z)` columns of a region in the order of a heightmap's values.
+fn heightmap_columns(region: &Region) -> impl Iterator<Item = Region> {
+    let (min_x, min_y, min_z) = region.min();
+    let (size_x, size_y, size_z) = region.size();
+    (0..size_z).flat_map(move |z| {
+        (0..size_x).map(move |x| Region::from_size((min_x + x, min_y, min_z + z), (1, size_y, 1)))
+    })
+}
+
+/// Visit the bars of a bar chart with `count` values: each bar is an equal share of the width of the
+/// region along x (at least one block), and the whole depth of the region along z.
+fn bar_columns(region: &Region, count: usize) -> impl Iterator<Item = Region> {
+    let (min_x, min_y, min_z) = region.min();
+    let (size_x, size_y, size_z) = region.size();
+    let width = (size_x / count.max(1) as i32).max(1);
+    (0..size_x / width).map(move |i| {
+        Region::from_size((min_x + i * width, min_y, min_z), (width, size_y, size_z))
+    })
+}
+
+/// Draw a heightmap in a region. Each value is the height in blocks of the column of `block` at one
+/// `(x, z)` position, starting from the bottom of the region, and the rest of the column is filled
+/// with air. Heights are clamped between 0 and the height of the region.
+///
+/// Values are in rows along x, with one row for each z from the minimum corner of the region, so the
+/// value for position `(x, z)` relative to the minimum corner is `heights[z * size_x + x]`. Extra
+/// values are ignored, and if there are too few values, the remaining columns are left unchanged.
+///
+/// This moves the turtle, so the turtle position is undefined after calling this function.
+pub fn render_heightmap(heights: &[i32], region: &Region, block: Block) {
+    let total = region.size().1;
+    for (height, column) in heights.iter().zip(heightmap_columns(region)) {
+        draw_column(&column, (*height).clamp(0, total), None, block);
+    }
+}
+
+/// Redraw a heightmap drawn by [`render_heightmap`] with the same region and block, only changing
+/// the blocks in columns whose height is different from `previous`.
+///
+/// This assumes that nothing else has modified the region since `previous` was drawn.
+///
+/// This moves the turtle, so the turtle position is undefined after calling this function.
+pub fn update_heightmap(heights: &[i32], previous: &[i32], region: &Region, block: Block) {
+    let total = region.size().1;
+    for ((height, previous), column) in heights.iter().zip(previous).zip(heightmap_columns(region)) {
+        let (height, previous) = ((*height).clamp(0, total), (*previous).clamp(0, total));
+        if height != previous {
+            draw_column(&column, height, Some(previous), block);
+        }
+    }
+}
+
+/// Draw a bar chart in a region, with one bar of `block` for each value, scaled so that a value of
+/// `max` fills the height of the region. Values are clamped between 0 and `max`, and the rest of
+/// each bar's column is filled with air.
+///
+/// The bars are placed side by side along x, starting from the minimum corner of the region, and
+/// each one takes up an equal share of the width of the region (rounded down, but at least one
+/// block) and the whole depth of the region along z. Values that do not fit in the region are
+/// ignored.
+///
+/// This moves the turtle, so the turtle position is undefined after calling this function.
+pub fn render_bar_chart(values: &[i32], max: i32, region: &Region, block: Block) {
+    let total = region.size().1;
+    for (value, bar) in values.iter().zip(bar_columns(region, values.len())) {
+        draw_column(&bar, bar_height(*value, max, total), None, block);
+    }
+}
+
+/// Redraw a bar chart drawn by [`render_bar_chart`] with the same `max`, region, block and number of
+/// values, only changing the blocks in bars whose height is different from the bar for the
+/// corresponding value in `previous`.
+///
+/// This assumes that nothing else has modified the region since `previous` was drawn. If `max` or
+/// the number of values changes, use [`render_bar_chart`] instead.
+///
+/// This moves the turtle, so the turtle position is undefined after calling this function.
+pub fn update_bar_chart(values: &[i32], previous: &[i32], max: i32, region: &Region, block: Block) {
+    let total = region.size().1;
+    for ((value, previous), bar) in values.iter().zip(previous).zip(bar_columns(region, values.len())) {
+        let (height, previous) = (bar_height(*value, max, total), bar_height(*previous, max, total));
+        if height != previous {
+            draw_column(&bar, height, Some(previous), block);
+        }
+    }
+}
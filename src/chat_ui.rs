@@ -0,0 +1,261 @@
+//! Progress bars, tables and rules printed to the game chat.
+//!
+//! Contains [`ProgressBar`], which prints lines like `Building [#####-----] 50%` as work progresses,
+//! [`Table`], which prints rows of text in aligned columns, and [`rule`] and [`heading`], which print
+//! horizontal separators.
+//!
+//! The chat uses a proportional font, so padding text with a fixed number of spaces does not line up
+//! columns. [`Table`] measures text with [`text_width`], which knows the width of each ASCII character
+//! in Minecraft's default font, and pads with as many spaces as come closest to the column width.
+//! Spaces are 4 pixels wide, so columns can still be off by up to 2 pixels.
+//!
+//! Nothing here uses `core::fmt`, so it is cheap to print even in tight loops.
+
+use crate::fmt::print_u32;
+use crate::{mc_putc, print_str};
+
+/// The width in pixels of a typical character (including the 1 pixel gap after it), used to convert
+/// column widths in characters to pixels.
+pub const CHAR_WIDTH: u32 = 6;
+
+/// The width in pixels of a space.
+const SPACE_WIDTH: u32 = 4;
+
+/// The width in pixels of a character in Minecraft's default chat font, including the 1 pixel gap
+/// after it. Characters outside ASCII are assumed to be [`CHAR_WIDTH`] wide.
+pub const fn char_width(c: char) -> u32 {
+    match c {
+        '!' | '\'' | ',' | '.' | ':' | ';' | 'i' | '|' => 2,
+        '`' | 'l' => 3,
+        ' ' | '"' | '(' | ')' | '*' | 'I' | '[' | ']' | 't' | '{' | '}' => 4,
+        '<' | '>' | 'f' | 'k' => 5,
+        '@' | '~' => 7,
+        _ => CHAR_WIDTH,
+    }
+}
+
+/// The width in pixels of a string in Minecraft's default chat font.
+pub fn text_width(s: &str) -> u32 {
+    s.chars().map(char_width).sum()
+}
+
+/// Print `count` copies of a character.
+fn repeat(c: char, count: u32) {
+    for _ in 0..count {
+        mc_putc(c);
+    }
+}
+
+/// Print enough spaces to cover `pixels` pixels, rounded to the nearest space.
+fn pad(pixels: u32) {
+    repeat(' ', (pixels + SPACE_WIDTH / 2) / SPACE_WIDTH);
+}
+
+/// Print a horizontal rule made of `width` dashes.
+pub fn rule(width: u32) {
+    repeat('-', width);
+    mc_putc('\n');
+}
+
+/// Print a title between two rules of `=` signs, like `===== Title =====`, about `width` typical
+/// characters wide in total.
+pub fn heading(title: &str, width: u32) {
+    let rule_pixels = (width * CHAR_WIDTH).saturating_sub(text_width(title) + 2 * SPACE_WIDTH);
+    // `=` is a typical-width character
+    let left = rule_pixels / CHAR_WIDTH / 2;
+    let right = (rule_pixels / CHAR_WIDTH).saturating_sub(left);
+    repeat('=', left.max(1));
+    mc_putc(' ');
+    print_str(title);
+    mc_putc(' ');
+    repeat('=', right.max(1));
+    mc_putc('\n');
+}
+
+/// A progress bar that prints a line each time the work passes another step of its progress.
+///
+/// Each line looks like `Label [#####-----] 50%`. By default the bar is 20 characters wide and a
+/// line is printed every 10%, starting with the first update. A line is always printed when the
+/// work is complete.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::chat_ui::ProgressBar;
+/// let mut progress = ProgressBar::new(1000).label("Building").step(25);
+/// for i in 0..1000 {
+///     // ...
+///     progress.inc(1);
+/// }
+/// ```
+pub struct ProgressBar<'a> {
+    label: &'a str,
+    total: u32,
+    current: u32,
+    width: u32,
+    step: u32,
+    /// The percentage at which the next line is printed.
+    next: u32,
+}
+
+impl<'a> ProgressBar<'a> {
+    /// Create a progress bar for `total` units of work. A total of 0 is treated as 1.
+    pub const fn new(total: u32) -> Self {
+        Self {
+            label: "",
+            total: if total == 0 { 1 } else { total },
+            current: 0,
+            width: 20,
+            step: 10,
+            next: 0,
+        }
+    }
+
+    /// Set the text printed before the bar.
+    pub const fn label(mut self, label: &'a str) -> Self {
+        self.label = label;
+        self
+    }
+
+    /// Set the width of the bar in characters (at least 1).
+    pub const fn width(mut self, width: u32) -> Self {
+        self.width = if width == 0 { 1 } else { width };
+        self
+    }
+
+    /// Set how often a line is printed, as a percentage of the total (between 1 and 100).
+    pub const fn step(mut self, percent: u32) -> Self {
+        self.step = if percent == 0 {
+            1
+        } else if percent > 100 {
+            100
+        } else {
+            percent
+        };
+        self
+    }
+
+    /// The percentage of the work done so far, rounded down.
+    pub fn percent(&self) -> u32 {
+        (self.current as u64 * 100 / self.total as u64) as u32
+    }
+
+    /// Set the amount of work done, printing a line if it has passed the next step.
+    pub fn set(&mut self, current: u32) {
+        self.current = current.min(self.total);
+        let percent = self.percent();
+        if percent >= self.next {
+            self.print();
+            // Skip any steps that were passed at once, and never print 100% twice
+            self.next = if percent >= 100 {
+                101
+            } else {
+                ((percent / self.step + 1) * self.step).min(100)
+            };
+        }
+    }
+
+    /// Add to the amount of work done, printing a line if it has passed the next step.
+    #[inline]
+    pub fn inc(&mut self, amount: u32) {
+        self.set(self.current.saturating_add(amount));
+    }
+
+    /// Mark the work as complete, printing the final line if it has not been printed yet.
+    #[inline]
+    pub fn finish(&mut self) {
+        self.set(self.total);
+    }
+
+    /// Print the current state of the bar, regardless of the step.
+    pub fn print(&self) {
+        if !self.label.is_empty() {
+            print_str(self.label);
+            mc_putc(' ');
+        }
+        let filled = (self.current as u64 * self.width as u64 / self.total as u64) as u32;
+        mc_putc('[');
+        repeat('#', filled);
+        repeat('-', self.width - filled);
+        print_str("] ");
+        print_u32(self.percent());
+        print_str("%\n");
+    }
+}
+
+/// The alignment of a column in a [`Table`].
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+}
+
+/// Prints rows of `C` cells in aligned columns.
+///
+/// Column widths are given in typical characters (see [`CHAR_WIDTH`]), and cells are padded with
+/// spaces to line up in the chat's proportional font. Text wider than its column is not cut off, and
+/// pushes the rest of the row to the right. Columns are separated by `" | "`.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::chat_ui::{Align, Table};
+/// let table = Table::new([10, 6]).align([Align::Left, Align::Right]);
+/// table.header(["Block", "Count"]);
+/// table.row(["Cobblestone", "1204"]);
+/// table.row(["Gold", "17"]);
+/// ```
+pub struct Table<const C: usize> {
+    widths: [u32; C],
+    align: [Align; C],
+}
+
+impl<const C: usize> Table<C> {
+    /// Create a table with the given column widths, in typical characters, with every column
+    /// aligned to the left.
+    pub const fn new(widths: [u32; C]) -> Self {
+        Self { widths, align: [Align::Left; C] }
+    }
+
+    /// Set the alignment of each column.
+    pub const fn align(mut self, align: [Align; C]) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// The total width of the table in typical characters, including separators.
+    fn total_width(&self) -> u32 {
+        let separators = C.saturating_sub(1) as u32 * text_width(" | ");
+        (self.widths.iter().sum::<u32>() * CHAR_WIDTH + separators).div_ceil(CHAR_WIDTH)
+    }
+
+    /// Print a row of cells.
+    pub fn row(&self, cells: [&str; C]) {
+        for (i, cell) in cells.iter().enumerate() {
+            if i > 0 {
+                print_str(" | ");
+            }
+            let padding = (self.widths[i] * CHAR_WIDTH).saturating_sub(text_width(cell));
+            let last = i == C - 1;
+            match self.align[i] {
+                Align::Left => {
+                    print_str(cell);
+                    // Trailing spaces on the last column are invisible anyway
+                    if !last {
+                        pad(padding);
+                    }
+                }
+                Align::Right => {
+                    pad(padding);
+                    print_str(cell);
+                }
+            }
+        }
+        mc_putc('\n');
+    }
+
+    /// Print a row of cells followed by a [`rule`] as wide as the table.
+    pub fn header(&self, cells: [&str; C]) {
+        self.row(cells);
+        rule(self.total_width());
+    }
+}
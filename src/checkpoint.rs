@@ -0,0 +1,127 @@
+//! Saving program state in the world.
+//!
+//! Contains [`Checkpoint`], which stores a [`Pod`] value in a [`BlockMemory`] so that long-running
+//! programs can resume where they left off after the datapack is reloaded.
+
+use crate::blockmem::BlockMemory;
+use crate::mem::{bytes_of, bytes_of_mut, zeroed};
+use crate::pod::Pod;
+use core::marker::PhantomData;
+use core::mem::size_of;
+
+/// Marks the start of a valid checkpoint.
+const MAGIC: i32 = 0x4d43_4350;
+
+/// The number of values stored before the data: the magic number, the data length and the checksum.
+const HEADER_LEN: usize = 3;
+
+/// A saved value of type `T`, stored in the world.
+///
+/// The value is stored along with a magic number, its length and a checksum, so [`Checkpoint::restore`]
+/// only returns a value if a complete checkpoint of the right type has been saved. Saving invalidates
+/// the old checkpoint before writing the new one, so a save that is interrupted (for example by the
+/// datapack being reloaded) is never restored.
+///
+/// The checkpoint uses [`Checkpoint::len`] values of its [`BlockMemory`], starting from index 0.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{blockmem::BlockMemory, checkpoint::Checkpoint};
+/// const CHECKPOINT: Checkpoint<State> = Checkpoint::new(BlockMemory::new((0, -60, 0), 16));
+///
+/// #[no_mangle]
+/// pub extern fn _start() -> i32 {
+///     let mut state = CHECKPOINT.restore().unwrap_or(State::INITIAL);
+///     loop {
+///         state.step();
+///         CHECKPOINT.save(&state);
+///     }
+/// }
+/// ```
+pub struct Checkpoint<T: Pod> {
+    memory: BlockMemory,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Pod> Checkpoint<T> {
+    /// The number of `i32` values needed to store the data.
+    const DATA_LEN: usize = size_of::<T>().div_ceil(4);
+
+    /// Create a checkpoint stored in the given memory.
+    #[inline(always)]
+    pub const fn new(memory: BlockMemory) -> Self {
+        Self {
+            memory,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The number of values of the [`BlockMemory`] used by the checkpoint.
+    #[allow(clippy::len_without_is_empty)]
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        HEADER_LEN + Self::DATA_LEN
+    }
+
+    /// Get the `i`th word of the value's bytes, padded with zeroes at the end.
+    fn word(bytes: &[u8], i: usize) -> i32 {
+        let mut word = [0; 4];
+        for (j, byte) in word.iter_mut().enumerate() {
+            *byte = bytes.get(i * 4 + j).copied().unwrap_or(0);
+        }
+        i32::from_le_bytes(word)
+    }
+
+    fn checksum(hash: u32, word: i32) -> u32 {
+        (hash ^ word as u32).wrapping_mul(16777619)
+    }
+
+    /// Save a value, replacing any previous checkpoint.
+    ///
+    /// This moves the turtle, so the turtle position is undefined after calling this function.
+    pub fn save(&self, value: &T) {
+        let bytes = bytes_of(value);
+        self.memory.write(0, 0);
+        let mut hash = 2166136261;
+        for i in 0..Self::DATA_LEN {
+            let word = Self::word(bytes, i);
+            hash = Self::checksum(hash, word);
+            self.memory.write(HEADER_LEN + i, word);
+        }
+        self.memory.write(1, Self::DATA_LEN as i32);
+        self.memory.write(2, hash as i32);
+        self.memory.write(0, MAGIC);
+    }
+
+    /// Restore the saved value, or return `None` if there is no valid checkpoint.
+    ///
+    /// This moves the turtle, so the turtle position is undefined after calling this function.
+    pub fn restore(&self) -> Option<T> {
+        if self.memory.read(0) != MAGIC || self.memory.read(1) != Self::DATA_LEN as i32 {
+            return None;
+        }
+        let mut value = zeroed::<T>();
+        let bytes = bytes_of_mut(&mut value);
+        let mut hash = 2166136261;
+        for i in 0..Self::DATA_LEN {
+            let word = self.memory.read(HEADER_LEN + i);
+            hash = Self::checksum(hash, word);
+            for (j, byte) in word.to_le_bytes().iter().enumerate() {
+                if let Some(b) = bytes.get_mut(i * 4 + j) {
+                    *b = *byte;
+                }
+            }
+        }
+        if self.memory.read(2) != hash as i32 {
+            return None;
+        }
+        Some(value)
+    }
+
+    /// Invalidate the saved checkpoint, so that [`Checkpoint::restore`] returns `None`.
+    ///
+    /// This moves the turtle, so the turtle position is undefined after calling this function.
+    pub fn clear(&self) {
+        self.memory.write(0, 0);
+    }
+}
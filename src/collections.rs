@@ -0,0 +1,294 @@
+//! Fixed-capacity collections that do not need an allocator.
+//!
+//! Contains [`FixedVec`], a vector with a capacity fixed at compile time, and [`FixedString`], a string
+//! built on top of it. Both store their contents inline, so they can live on the stack or in a
+//! `static`.
+//!
+//! [`FixedString`] implements [`Write`](core::fmt::Write), so it can be used as a scratch buffer with
+//! [`write!`] and then printed with [`FixedString::print`] or [`FixedString::println`].
+
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+
+/// The error returned when adding to a full [`FixedVec`] or [`FixedString`].
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct CapacityError;
+
+impl core::fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("insufficient capacity")
+    }
+}
+
+/// A vector that can hold up to `N` elements of type `T`, stored inline.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::collections::FixedVec;
+/// let mut v: FixedVec<i32, 8> = FixedVec::new();
+/// v.push(1).unwrap();
+/// v.push(2).unwrap();
+/// assert_eq!(&v[..], &[1, 2]);
+/// ```
+pub struct FixedVec<T, const N: usize> {
+    data: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> FixedVec<T, N> {
+    /// Create a new empty vector.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            data: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        }
+    }
+
+    /// The maximum number of elements the vector can hold.
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Check whether the vector is full.
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Add an element to the end of the vector, or return it as an error if the vector is full.
+    #[inline]
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        match self.data.get_mut(self.len) {
+            Some(slot) => {
+                slot.write(value);
+                self.len += 1;
+                Ok(())
+            }
+            None => Err(value),
+        }
+    }
+
+    /// Remove the last element of the vector and return it, or `None` if the vector is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: elements below `len` are initialised, and this one is no longer considered part of
+        // the vector so will not be read again
+        Some(unsafe { self.data[self.len].assume_init_read() })
+    }
+
+    /// Shorten the vector to `len` elements, dropping the rest. Does nothing if the vector is already
+    /// shorter than `len`.
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            self.pop();
+        }
+    }
+
+    /// Remove every element from the vector.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Get the contents of the vector as a slice.
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: elements below `len` are initialised
+        unsafe { core::slice::from_raw_parts(self.data.as_ptr() as *const T, self.len) }
+    }
+
+    /// Get the contents of the vector as a mutable slice.
+    #[inline(always)]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: elements below `len` are initialised
+        unsafe { core::slice::from_raw_parts_mut(self.data.as_mut_ptr() as *mut T, self.len) }
+    }
+}
+
+impl<T: Clone, const N: usize> FixedVec<T, N> {
+    /// Append clones of the elements of a slice, returning an error without changing the vector if
+    /// they do not all fit.
+    pub fn extend_from_slice(&mut self, other: &[T]) -> Result<(), CapacityError> {
+        if self.len + other.len() > N {
+            return Err(CapacityError);
+        }
+        for value in other {
+            let _ = self.push(value.clone());
+        }
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> Drop for FixedVec<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Default for FixedVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Deref for FixedVec<T, N> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for FixedVec<T, N> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for FixedVec<T, N> {
+    fn clone(&self) -> Self {
+        let mut clone = Self::new();
+        let _ = clone.extend_from_slice(self);
+        clone
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for FixedVec<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for FixedVec<T, N> {}
+
+impl<T: core::fmt::Debug, const N: usize> core::fmt::Debug for FixedVec<T, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+/// A string that can hold up to `N` bytes of UTF-8, stored inline.
+///
+/// Writing more than `N` bytes with [`write!`] returns an error, and the text that did not fit is
+/// discarded.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::collections::FixedString;
+/// # use core::fmt::Write;
+/// let mut s: FixedString<32> = FixedString::new();
+/// write!(s, "score: {}", 42).unwrap();
+/// s.println();
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct FixedString<const N: usize> {
+    bytes: FixedVec<u8, N>,
+}
+
+impl<const N: usize> FixedString<N> {
+    /// Create a new empty string.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            bytes: FixedVec::new(),
+        }
+    }
+
+    /// The maximum number of bytes the string can hold.
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// The length of the string in bytes.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Check whether the string is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Get the contents of the string as a `&str`.
+    #[inline(always)]
+    pub fn as_str(&self) -> &str {
+        // SAFETY: only complete UTF-8 strings are ever added to the buffer
+        unsafe { core::str::from_utf8_unchecked(&self.bytes) }
+    }
+
+    /// Append a string, returning an error without changing this string if it does not fit.
+    #[inline]
+    pub fn push_str(&mut self, s: &str) -> Result<(), CapacityError> {
+        self.bytes.extend_from_slice(s.as_bytes())
+    }
+
+    /// Append a character, returning an error without changing this string if it does not fit.
+    #[inline]
+    pub fn push(&mut self, c: char) -> Result<(), CapacityError> {
+        self.push_str(c.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Remove the last character of the string and return it, or `None` if the string is empty.
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.as_str().chars().next_back()?;
+        self.bytes.truncate(self.len() - c.len_utf8());
+        Some(c)
+    }
+
+    /// Remove the contents of the string.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+
+    /// Print the string to the game chat. See [`print_str()`](crate::print_str).
+    #[inline]
+    pub fn print(&self) {
+        crate::print_str(self.as_str());
+    }
+
+    /// Print the string to the game chat, with a newline. See [`println()`](crate::println).
+    #[inline]
+    pub fn println(&self) {
+        crate::println(self.as_str());
+    }
+}
+
+impl<const N: usize> Deref for FixedString<N> {
+    type Target = str;
+
+    #[inline(always)]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> core::fmt::Write for FixedString<N> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s).map_err(|_| core::fmt::Error)
+    }
+}
+
+impl<const N: usize> core::fmt::Display for FixedString<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for FixedString<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
@@ -0,0 +1,185 @@
+//! A scrolling text console drawn with blocks in the world.
+//!
+//! Contains [`BlockConsole`], which prints text onto a wall of blocks using the built-in
+//! [font](crate::text), wrapping long lines and scrolling older lines upwards once the console is
+//! full. Unlike the game chat, the text stays in place until it is scrolled away, which makes it
+//! useful for status displays.
+//!
+//! The console draws into a [`Screen`], so each [`BlockConsole::flush`] only places the blocks that
+//! have changed since the last one.
+
+use crate::screen::Screen;
+use crate::text::{GLYPH_WIDTH, LINE_HEIGHT};
+use crate::Block;
+use core::fmt::Write;
+
+/// The number of columns between tab stops.
+const TAB_WIDTH: usize = 4;
+
+/// A text console on a `W` by `H` block wall in the XY plane.
+///
+/// The console has `W / GLYPH_WIDTH` columns and `H / LINE_HEIGHT` rows of text (see
+/// [`GLYPH_WIDTH`] and [`LINE_HEIGHT`]). Text that reaches the right edge wraps onto the next line,
+/// and printing past the last line scrolls the whole console up by one line. `'\r'` returns to the
+/// start of the current line and `'\t'` moves to the next multiple of 4 columns.
+///
+/// Text is drawn into a [`Screen`], and only appears in the world after [`BlockConsole::flush`]. It
+/// implements [`Write`], so it can also be used with the [`write!`] and [`writeln!`] macros.
+///
+/// Like [`Screen`], each block of the console takes up 8 bytes of memory.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{Block, console::BlockConsole};
+/// # use core::fmt::Write;
+/// let mut console: BlockConsole<80, 32> = BlockConsole::new(0, 100, 0, Block::Gold, Block::Air);
+/// console.println("Status:");
+/// writeln!(console, "tick {}", 42).unwrap();
+/// console.flush();
+/// ```
+pub struct BlockConsole<const W: usize, const H: usize> {
+    screen: Screen<W, H>,
+    foreground: Block,
+    background: Block,
+    column: usize,
+    row: usize,
+    /// Whether a newline has been printed but not yet acted on. Moving to the next line is delayed
+    /// until the next character, so that a line ending in a newline does not scroll away the top line
+    /// straight away.
+    pending_newline: bool,
+}
+
+impl<const W: usize, const H: usize> BlockConsole<W, H> {
+    /// The number of characters that fit on each line.
+    pub const COLUMNS: usize = W / GLYPH_WIDTH;
+
+    /// The number of lines of text.
+    pub const ROWS: usize = H / LINE_HEIGHT;
+
+    /// Create a new, empty console whose bottom left corner is at the given world position. Text is
+    /// drawn with `foreground` on top of `background`.
+    ///
+    /// Like [`Screen::new`], the console assumes that its area of the world is currently filled with
+    /// air, so a background other than air is placed on the first [`BlockConsole::flush`].
+    ///
+    /// # Panics
+    /// Panics if the console is too small to fit a single character.
+    pub fn new(x: i32, y: i32, z: i32, foreground: Block, background: Block) -> Self {
+        assert!(Self::COLUMNS > 0 && Self::ROWS > 0, "console too small to fit a character");
+        let mut screen = Screen::new(x, y, z);
+        screen.clear(background);
+        Self {
+            screen,
+            foreground,
+            background,
+            column: 0,
+            row: 0,
+            pending_newline: false,
+        }
+    }
+
+    /// The [`Screen`] the console draws into, for drawing other things alongside the text.
+    #[inline(always)]
+    pub fn screen(&mut self) -> &mut Screen<W, H> {
+        &mut self.screen
+    }
+
+    /// The line and column at which the next character will be printed.
+    #[inline]
+    pub fn cursor(&self) -> (usize, usize) {
+        if self.pending_newline {
+            ((self.row + 1).min(Self::ROWS - 1), 0)
+        } else {
+            (self.row, self.column)
+        }
+    }
+
+    /// Set the block used to draw text printed from now on.
+    #[inline(always)]
+    pub fn set_foreground(&mut self, foreground: Block) {
+        self.foreground = foreground;
+    }
+
+    /// Move to the start of the next line, scrolling the console up if this is the last line.
+    fn newline(&mut self) {
+        self.column = 0;
+        if self.row + 1 < Self::ROWS {
+            self.row += 1;
+        } else {
+            self.screen.scroll_up(LINE_HEIGHT, self.background);
+        }
+    }
+
+    /// Print a single character.
+    pub fn put_char(&mut self, c: char) {
+        if self.pending_newline {
+            self.pending_newline = false;
+            self.newline();
+        }
+        match c {
+            '\n' => self.pending_newline = true,
+            '\r' => self.column = 0,
+            '\t' => {
+                let next = (self.column / TAB_WIDTH + 1) * TAB_WIDTH;
+                while self.column < next.min(Self::COLUMNS) {
+                    self.put_char(' ');
+                }
+            }
+            _ => {
+                if self.column == Self::COLUMNS {
+                    self.newline();
+                }
+                self.screen.draw_text(
+                    self.column * GLYPH_WIDTH,
+                    self.row * LINE_HEIGHT,
+                    c.encode_utf8(&mut [0; 4]),
+                    self.foreground,
+                    Some(self.background),
+                );
+                self.column += 1;
+            }
+        }
+    }
+
+    /// Print a string.
+    pub fn print(&mut self, s: &str) {
+        for c in s.chars() {
+            self.put_char(c);
+        }
+    }
+
+    /// Print a string followed by a newline.
+    #[inline]
+    pub fn println(&mut self, s: &str) {
+        self.print(s);
+        self.put_char('\n');
+    }
+
+    /// Erase all text and move the cursor back to the top left corner.
+    pub fn clear(&mut self) {
+        self.screen.clear(self.background);
+        self.column = 0;
+        self.row = 0;
+        self.pending_newline = false;
+    }
+
+    /// Place every block that has changed since the last flush into the world.
+    ///
+    /// This moves the turtle, so the turtle position is undefined after calling this function.
+    #[inline]
+    pub fn flush(&mut self) {
+        self.screen.flush();
+    }
+}
+
+impl<const W: usize, const H: usize> Write for BlockConsole<W, H> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.print(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> core::fmt::Result {
+        self.put_char(c);
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+//! Dumping memory to the game chat.
+//!
+//! [`dump_hex`] prints bytes as lines of hexadecimal with their offset and an ASCII column, like
+//! `xxd`, and [`dump_base64`] prints them as Base64 (which is more compact, and can be copied out of
+//! the chat log and decoded elsewhere). [`dump_value`] dumps the bytes of any [`Pod`] value.
+//!
+//! Lines are kept short enough to fit in the chat without wrapping. Each line costs a few hundred
+//! commands, so large dumps are spread over several ticks with
+//! [`yield_if_needed`](crate::pacing::yield_if_needed).
+//!
+//! Usage:
+//! ```ignore
+//! # use mcinterface::dump::dump_hex;
+//! dump_hex(b"Hello, World!");
+//! // dump of 13 bytes at 0x00001234
+//! // 0000: 48 65 6c 6c 6f 2c 20 57  Hello, W
+//! // 0008: 6f 72 6c 64 21           orld!
+//! ```
+
+use crate::fmt::print_hex_padded;
+use crate::mem::bytes_of;
+use crate::pacing::yield_if_needed;
+use crate::pod::Pod;
+use crate::{mc_putc, print_str};
+
+/// The number of bytes on each line of a hex dump.
+pub const HEX_LINE_BYTES: usize = 8;
+
+/// The number of bytes on each line of a Base64 dump (encoded as 40 characters).
+pub const BASE64_LINE_BYTES: usize = 30;
+
+/// The approximate number of commands needed to print a line, for
+/// [`yield_if_needed`](crate::pacing::yield_if_needed).
+const LINE_COST: u32 = 400;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Print a line saying what is being dumped.
+fn header(bytes: &[u8]) {
+    print_str("dump of ");
+    crate::fmt::print_u32(bytes.len() as u32);
+    print_str(" bytes at 0x");
+    print_hex_padded(bytes.as_ptr() as usize as u32, 8);
+    mc_putc('\n');
+}
+
+/// Print bytes in hexadecimal, [`HEX_LINE_BYTES`] per line. Each line starts with the offset of its
+/// first byte (in hexadecimal), and ends with the bytes as ASCII, with unprintable bytes shown as
+/// `.`. The first line gives the length and address of the bytes.
+pub fn dump_hex(bytes: &[u8]) {
+    header(bytes);
+    for (line, chunk) in bytes.chunks(HEX_LINE_BYTES).enumerate() {
+        yield_if_needed(LINE_COST);
+        print_hex_padded((line * HEX_LINE_BYTES) as u32, 4);
+        mc_putc(':');
+        for byte in chunk {
+            mc_putc(' ');
+            print_hex_padded(*byte as u32, 2);
+        }
+        // Line up the ASCII column on a short last line
+        for _ in chunk.len()..HEX_LINE_BYTES {
+            print_str("   ");
+        }
+        print_str("  ");
+        for byte in chunk {
+            mc_putc(if byte.is_ascii_graphic() || *byte == b' ' { *byte as char } else { '.' });
+        }
+        mc_putc('\n');
+    }
+}
+
+/// Print bytes as standard Base64 (with `+`, `/` and `=` padding), [`BASE64_LINE_BYTES`] per line.
+/// Every line except the last is a whole number of 3-byte groups, so the lines can be joined
+/// together and decoded as one string. The first line gives the length and address of the bytes.
+pub fn dump_base64(bytes: &[u8]) {
+    header(bytes);
+    for chunk in bytes.chunks(BASE64_LINE_BYTES) {
+        yield_if_needed(LINE_COST);
+        for group in chunk.chunks(3) {
+            let b = [group[0], *group.get(1).unwrap_or(&0), *group.get(2).unwrap_or(&0)];
+            let bits = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+            for i in 0..4 {
+                if i <= group.len() {
+                    mc_putc(BASE64_ALPHABET[(bits >> (18 - 6 * i) & 63) as usize] as char);
+                } else {
+                    mc_putc('=');
+                }
+            }
+        }
+        mc_putc('\n');
+    }
+}
+
+/// Print the bytes of a value in hexadecimal with [`dump_hex`].
+#[inline]
+pub fn dump_value<T: Pod>(value: &T) {
+    dump_hex(bytes_of(value));
+}
+
+/// Print `len` bytes of memory starting at `ptr` in hexadecimal with [`dump_hex`].
+///
+/// # Safety
+/// `ptr` must be valid for reads of `len` bytes, and the bytes must be initialised.
+#[inline]
+pub unsafe fn dump_hex_raw(ptr: *const u8, len: usize) {
+    dump_hex(core::slice::from_raw_parts(ptr, len));
+}
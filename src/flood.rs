@@ -0,0 +1,132 @@
+//! Flood filling connected areas of blocks.
+//!
+//! Contains [`flood_fill`], which replaces every block of one type that is connected to a starting
+//! position, e.g. to fill a cave with stone or to repaint one part of a build. A recursive fill would
+//! overflow wasmcraft2's small stack almost immediately, so this uses a breadth-first search with a
+//! queue of positions that still need to be visited (the frontier). The queue only needs to hold
+//! roughly the surface of the area filled so far, not the whole area, but even that is too large to
+//! keep on the stack, so the caller provides the buffer for it (usually a `static`, or a `Vec` with
+//! the `alloc` feature).
+
+use crate::region::Region;
+use crate::vec::IVec3;
+use crate::{turtle_check, turtle_pos_vec, turtle_set, Block};
+
+/// The error returned by [`flood_fill`] when its frontier queue runs out of space.
+///
+/// The fill has stopped part way through, so some of the connected blocks have not been replaced.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct FrontierFull {
+    /// The number of blocks that were replaced before the queue filled up.
+    pub filled: u32,
+}
+
+impl core::fmt::Display for FrontierFull {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "flood fill frontier full after {} blocks", self.filled)
+    }
+}
+
+/// A first-in, first-out queue of positions in a borrowed buffer.
+struct Queue<'a> {
+    items: &'a mut [IVec3],
+    head: usize,
+    len: usize,
+}
+
+impl Queue<'_> {
+    fn push(&mut self, pos: IVec3) -> bool {
+        let capacity = self.items.len();
+        if self.len == capacity {
+            return false;
+        }
+        let mut tail = self.head + self.len;
+        if tail >= capacity {
+            tail -= capacity;
+        }
+        self.items[tail] = pos;
+        self.len += 1;
+        true
+    }
+
+    fn pop(&mut self) -> Option<IVec3> {
+        if self.len == 0 {
+            return None;
+        }
+        let pos = self.items[self.head];
+        self.head += 1;
+        if self.head == self.items.len() {
+            self.head = 0;
+        }
+        self.len -= 1;
+        Some(pos)
+    }
+}
+
+/// Replace every `target` block inside `region` that is connected to `from` (through the faces of
+/// other `target` blocks in the region) with `replacement`, using [`turtle_check`] and
+/// [`turtle_set`]. Returns the number of blocks replaced, which is 0 if `from` is outside the region
+/// or does not contain `target`, or if `target` and `replacement` are the same.
+///
+/// `frontier` is the buffer for the frontier queue, and its length is the number of positions the
+/// queue can hold. Its contents are overwritten. A queue as large as the largest cross-section of the
+/// area is usually enough; for example, filling a flat 64x64 area from a corner needs 64. If the
+/// queue fills up, the fill stops and [`FrontierFull`] is returned.
+///
+/// Each position takes 12 bytes, so a frontier of more than a few dozen positions should not be a
+/// local array: with the recommended 4096 byte stack, a `[IVec3; 512]` (6 KB) on the stack overflows
+/// it. Use a `static` instead, or a `Vec` with the `alloc` feature.
+///
+/// This moves the turtle.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{flood::flood_fill, region::Region, vec::IVec3, Block};
+/// static mut FRONTIER: [IVec3; 512] = [IVec3::ZERO; 512];
+///
+/// // Fill the air in a cave with cobblestone
+/// let cave = Region::from_corners((-20, 20, -20), (20, 60, 20));
+/// let frontier = unsafe { &mut *core::ptr::addr_of_mut!(FRONTIER) };
+/// let filled = flood_fill(&cave, (0, 40, 0), Block::Air, Block::Cobblestone, frontier).unwrap();
+/// ```
+pub fn flood_fill(
+    region: &Region,
+    from: impl Into<IVec3>,
+    target: Block,
+    replacement: Block,
+    frontier: &mut [IVec3],
+) -> Result<u32, FrontierFull> {
+    let from = from.into();
+    if target == replacement || !region.contains(from) {
+        return Ok(0);
+    }
+    turtle_pos_vec(from);
+    if !turtle_check(target) {
+        return Ok(0);
+    }
+
+    // Blocks are replaced as they are queued, so that each one is queued at most once
+    let mut queue = Queue { items: frontier, head: 0, len: 0 };
+    if !queue.push(from) {
+        return Err(FrontierFull { filled: 0 });
+    }
+    turtle_set(replacement);
+    let mut filled = 1;
+
+    while let Some(pos) = queue.pop() {
+        for neighbour in pos.neighbours() {
+            if !region.contains(neighbour) {
+                continue;
+            }
+            turtle_pos_vec(neighbour);
+            if turtle_check(target) {
+                if !queue.push(neighbour) {
+                    return Err(FrontierFull { filled });
+                }
+                turtle_set(replacement);
+                filled += 1;
+            }
+        }
+    }
+    Ok(filled)
+}
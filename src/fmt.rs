@@ -2,7 +2,23 @@
 //!
 //! Contains [`MciWriteStream`], a unit struct which implements [`Write`] allowing it to be used
 //! with the [`write!`] macro, as well as implementations of [`print!`] and [`println!`] based on
-//! this.
+//! this. [`BufferedWriter`] collects a line in memory before printing it, so that output from
+//! different parts of a program is not mixed together on the same line.
+//!
+//! Formatting with `core::fmt` generates a lot of code, which is slow to run in Minecraft and makes
+//! datapacks much larger. For printing integers, [`print_i32`], [`print_u32`], [`print_i64`],
+//! [`print_u64`], [`print_hex`], [`print_bin`] and their padded variants avoid `core::fmt` entirely, and [`print!`] and
+//! [`println!`] use them automatically when given a format string of exactly `"{}"` and a single
+//! integer argument.
+//!
+//! With the `fixed` feature enabled, [`print_fixed`] and [`display_fixed`] print
+//! [`FixedI32`](fixed::FixedI32) values from the [`fixed`](https://docs.rs/fixed/latest/fixed/)
+//! crate as decimals using only integer arithmetic.
+//!
+//! With the `ufmt` feature enabled, [`MciWriteStream`] also implements [`ufmt::uWrite`], and the
+//! [`uprint!`](crate::uprint) and [`uprintln!`](crate::uprintln) macros can be used as lightweight
+//! replacements for [`print!`] and [`println!`] based on [`ufmt`](https://docs.rs/ufmt), which
+//! generates far less code than `core::fmt`.
 
 use core::fmt::Write;
 
@@ -36,12 +52,525 @@ impl Write for MciWriteStream {
     }
 }
 
+#[cfg(feature = "ufmt")]
+impl ufmt::uWrite for MciWriteStream {
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        crate::print_str(s);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn write_char(&mut self, c: char) -> Result<(), Self::Error> {
+        crate::mc_putc(c);
+        Ok(())
+    }
+}
+
+/// Implementation detail of [`uprint!`](crate::uprint) and [`uprintln!`](crate::uprintln), so that
+/// they work without a direct dependency on `ufmt`.
+#[cfg(feature = "ufmt")]
+#[doc(hidden)]
+pub use ufmt as __ufmt;
+
+/// The maximum number of characters [`BufferedWriter`] puts on a single line of chat before starting
+/// a new one.
+pub const CHAT_LINE_LIMIT: usize = 256;
+
+/// A writer that collects up to `N` characters in WASM memory and only passes them to
+/// [`mc_putc`](crate::mc_putc) when a line is complete.
+///
+/// Characters written with [`mc_putc`](crate::mc_putc) or [`print!`] go straight into the game's
+/// line buffer, so if two parts of a program each write part of a line, their output ends up mixed
+/// together on the same line. A [`BufferedWriter`] keeps its own line until a newline is written or
+/// [`BufferedWriter::flush`] is called, and then outputs the whole line at once. Lines longer than
+/// [`CHAT_LINE_LIMIT`] are automatically split over several lines of chat.
+///
+/// If more than `N` characters are written without ending the line, the buffered characters are
+/// passed on without ending the line, so `N` should be at least as long as the longest expected
+/// line. The writer is flushed when it is dropped.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::fmt::BufferedWriter;
+/// # use core::fmt::Write;
+/// let mut out: BufferedWriter<64> = BufferedWriter::new();
+/// write!(out, "score: ").unwrap();
+/// write!(out, "{}", 42).unwrap();
+/// out.flush(); // prints "score: 42"
+/// ```
+pub struct BufferedWriter<const N: usize> {
+    buffer: [u8; N],
+    len: usize,
+    /// The number of characters already passed to `mc_putc` on the current line
+    column: usize,
+}
+
+impl<const N: usize> BufferedWriter<N> {
+    /// Create a new, empty writer.
+    pub const fn new() -> Self {
+        Self {
+            buffer: [0; N],
+            len: 0,
+            column: 0,
+        }
+    }
+
+    /// The number of characters currently buffered.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no characters are currently buffered.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Discard the buffered characters without printing them.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Write a single character. Writing `'\n'` prints the buffered line.
+    pub fn push(&mut self, c: char) {
+        if c == '\n' {
+            self.flush();
+            return;
+        }
+        if self.len == N {
+            self.write_out();
+        }
+        if self.column + self.len == CHAT_LINE_LIMIT {
+            self.flush();
+        }
+        if N == 0 {
+            crate::mc_putc(c);
+            self.column += 1;
+            return;
+        }
+        // Any non-ASCII character is printed as a replacement character by wasmcraft2, and so is DEL
+        self.buffer[self.len] = if c.is_ascii() { c as u8 } else { 0x7f };
+        self.len += 1;
+    }
+
+    /// Write a string. Every `'\n'` in the string prints the buffered line.
+    pub fn push_str(&mut self, s: &str) {
+        for c in s.chars() {
+            self.push(c);
+        }
+    }
+
+    /// Print the buffered characters, followed by a newline so that they appear in chat. Does nothing
+    /// if the current line is empty.
+    pub fn flush(&mut self) {
+        if self.len > 0 || self.column > 0 {
+            self.write_out();
+            crate::mc_putc('\n');
+            self.column = 0;
+        }
+    }
+
+    /// Pass the buffered characters to `mc_putc` without ending the line.
+    fn write_out(&mut self) {
+        for b in &self.buffer[..self.len] {
+            crate::mc_putc(*b as char);
+        }
+        self.column += self.len;
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Default for BufferedWriter<N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Drop for BufferedWriter<N> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+impl<const N: usize> Write for BufferedWriter<N> {
+    #[inline(always)]
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn write_char(&mut self, c: char) -> core::fmt::Result {
+        self.push(c);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<const N: usize> ufmt::uWrite for BufferedWriter<N> {
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.push_str(s);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn write_char(&mut self, c: char) -> Result<(), Self::Error> {
+        self.push(c);
+        Ok(())
+    }
+}
+
+/// Write an unsigned integer in the given radix (between 2 and 16) one character at a time to `put`,
+/// padded on the left with `fill` to at least `width` characters.
+fn write_radix(mut value: u32, radix: u32, width: usize, fill: char, mut put: impl FnMut(char)) {
+    let mut digits = [0u8; 32];
+    let mut start = digits.len();
+    loop {
+        start -= 1;
+        digits[start] = b"0123456789abcdef"[(value % radix) as usize];
+        value /= radix;
+        if value == 0 {
+            break;
+        }
+    }
+    for _ in digits.len() - start..width {
+        put(fill);
+    }
+    for digit in &digits[start..] {
+        put(*digit as char);
+    }
+}
+
+#[inline(always)]
+fn print_radix(value: u32, radix: u32, width: usize, fill: char) {
+    write_radix(value, radix, width, fill, crate::mc_putc);
+}
+
+/// Print an unsigned integer to the game chat, without using `core::fmt`. Any printed characters will
+/// not appear until a newline (`'\n'`) is written.
+///
+/// Unlike [`print()`](crate::print), this does not end the line, so it can be combined with other
+/// text.
+#[inline]
+pub fn print_u32(value: u32) {
+    print_radix(value, 10, 0, '0');
+}
+
+/// Print a signed integer to the game chat, without using `core::fmt`. Any printed characters will
+/// not appear until a newline (`'\n'`) is written.
+///
+/// Unlike [`print()`](crate::print), this does not end the line, so it can be combined with other
+/// text.
+pub fn print_i32(value: i32) {
+    if value < 0 {
+        crate::mc_putc('-');
+    }
+    print_u32(value.unsigned_abs());
+}
+
+/// Print an unsigned 64-bit integer to the game chat, without using `core::fmt`. Any printed
+/// characters will not appear until a newline (`'\n'`) is written.
+///
+/// The value is converted to decimal by repeatedly dividing it by 10000 as four 16-bit limbs, so only
+/// 32-bit divisions are needed; 64-bit division is much more expensive in wasmcraft2.
+pub fn print_u64(value: u64) {
+    let mut limbs = [
+        (value >> 48) as u32,
+        (value >> 32) as u32 & 0xffff,
+        (value >> 16) as u32 & 0xffff,
+        value as u32 & 0xffff,
+    ];
+    // u64::MAX has 20 digits, which is 5 groups of 4
+    let mut groups = [0u32; 5];
+    let mut len = 0;
+    loop {
+        let mut remainder = 0;
+        for limb in limbs.iter_mut() {
+            // remainder < 10000, so this is less than 10000 * 2^16 and fits in a u32
+            let current = (remainder << 16) | *limb;
+            *limb = current / 10000;
+            remainder = current % 10000;
+        }
+        groups[len] = remainder;
+        len += 1;
+        if limbs == [0; 4] {
+            break;
+        }
+    }
+    print_u32(groups[len - 1]);
+    for group in groups[..len - 1].iter().rev() {
+        print_radix(*group, 10, 4, '0');
+    }
+}
+
+/// Print a signed 64-bit integer to the game chat, without using `core::fmt`. Any printed characters
+/// will not appear until a newline (`'\n'`) is written.
+///
+/// See [`print_u64`] for details.
+pub fn print_i64(value: i64) {
+    if value < 0 {
+        crate::mc_putc('-');
+    }
+    print_u64(value.unsigned_abs());
+}
+
+/// Print an unsigned integer, padded on the left with `fill` to at least `width` characters, without
+/// using `core::fmt`. Equivalent to `print!("{:>width$}", value)` when `fill` is a space, or
+/// `print!("{:0width$}", value)` when `fill` is `'0'`.
+#[inline]
+pub fn print_u32_padded(value: u32, width: usize, fill: char) {
+    print_radix(value, 10, width, fill);
+}
+
+/// Print a signed integer, padded on the left with `fill` to at least `width` characters (including
+/// the sign), without using `core::fmt`. When `fill` is `'0'`, the sign is printed before the padding,
+/// like `print!("{:0width$}", value)`.
+pub fn print_i32_padded(value: i32, width: usize, fill: char) {
+    if value < 0 {
+        if fill == '0' {
+            crate::mc_putc('-');
+            print_radix(value.unsigned_abs(), 10, width.saturating_sub(1), fill);
+        } else {
+            let digits = value.unsigned_abs().checked_ilog10().unwrap_or(0) as usize + 1;
+            for _ in digits + 1..width {
+                crate::mc_putc(fill);
+            }
+            crate::mc_putc('-');
+            print_u32(value.unsigned_abs());
+        }
+    } else {
+        print_radix(value as u32, 10, width, fill);
+    }
+}
+
+/// Print an integer in lowercase hexadecimal, without a prefix or padding, without using `core::fmt`.
+/// Equivalent to `print!("{:x}", value)`.
+#[inline]
+pub fn print_hex(value: u32) {
+    print_radix(value, 16, 0, '0');
+}
+
+/// Print an integer in lowercase hexadecimal, padded with zeroes to at least `width` digits, without
+/// using `core::fmt`. Equivalent to `print!("{:0width$x}", value)`; use a width of 8 to print every
+/// digit of a `u32`.
+#[inline]
+pub fn print_hex_padded(value: u32, width: usize) {
+    print_radix(value, 16, width, '0');
+}
+
+/// Print an integer in binary, without a prefix or padding, without using `core::fmt`. Equivalent to
+/// `print!("{:b}", value)`.
+#[inline]
+pub fn print_bin(value: u32) {
+    print_radix(value, 2, 0, '0');
+}
+
+/// Print an integer in binary, padded with zeroes to at least `width` digits, without using
+/// `core::fmt`. Equivalent to `print!("{:0width$b}", value)`; use a width of 32 to print every bit of
+/// a `u32`.
+#[inline]
+pub fn print_bin_padded(value: u32, width: usize) {
+    print_radix(value, 2, width, '0');
+}
+
+/// Write a fixed-point number with `frac_bits` fractional bits to `put` as a decimal with exactly
+/// `decimals` digits after the decimal point, rounding towards zero.
+#[cfg(feature = "fixed")]
+fn write_fixed(bits: i32, frac_bits: u32, decimals: usize, mut put: impl FnMut(char)) {
+    if bits < 0 {
+        put('-');
+    }
+    let magnitude = bits.unsigned_abs() as u64;
+    write_radix((magnitude >> frac_bits) as u32, 10, 0, '0', &mut put);
+    if decimals > 0 {
+        put('.');
+        let mask = (1u64 << frac_bits) - 1;
+        let mut frac = magnitude & mask;
+        for _ in 0..decimals {
+            // frac < 2^32, so this cannot overflow
+            frac *= 10;
+            put((b'0' + (frac >> frac_bits) as u8) as char);
+            frac &= mask;
+        }
+    }
+}
+
+/// Print a [`FixedI32`](fixed::FixedI32) to the game chat as a decimal with exactly `decimals` digits
+/// after the decimal point (rounding towards zero), without using `core::fmt` or floating point
+/// operations. Any printed characters will not appear until a newline (`'\n'`) is written.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::fmt::print_fixed;
+/// # use fixed::types::I16F16;
+/// print_fixed(I16F16::from_num(-3) / 8, 3); // prints -0.375
+/// ```
+#[cfg(feature = "fixed")]
+pub fn print_fixed<Frac: fixed::types::extra::LeEqU32>(value: fixed::FixedI32<Frac>, decimals: usize) {
+    write_fixed(value.to_bits(), fixed::FixedI32::<Frac>::FRAC_NBITS, decimals, crate::mc_putc);
+}
+
+/// Adapter returned by [`display_fixed`] that implements [`Display`](core::fmt::Display) (and
+/// `ufmt::uDisplay` with the `ufmt` feature) for a [`FixedI32`](fixed::FixedI32), using only integer
+/// arithmetic.
+#[cfg(feature = "fixed")]
+#[derive(Copy, Clone)]
+pub struct DisplayFixed<Frac> {
+    value: fixed::FixedI32<Frac>,
+    decimals: usize,
+}
+
+/// Format a [`FixedI32`](fixed::FixedI32) as a decimal with exactly `decimals` digits after the
+/// decimal point (rounding towards zero), for use with [`print!`], [`write!`] or
+/// [`uprint!`](crate::uprint).
+///
+/// The `Display` implementation in the `fixed` crate relies on 128-bit arithmetic and prints as many
+/// digits as are needed to represent the value exactly; this only uses 64-bit integer operations.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{fmt::display_fixed, println};
+/// # use fixed::types::I16F16;
+/// let speed = I16F16::from_num(5) / 3;
+/// println!("speed: {} blocks/tick", display_fixed(speed, 2)); // speed: 1.66 blocks/tick
+/// ```
+#[cfg(feature = "fixed")]
+#[inline]
+pub fn display_fixed<Frac: fixed::types::extra::LeEqU32>(
+    value: fixed::FixedI32<Frac>,
+    decimals: usize,
+) -> DisplayFixed<Frac> {
+    DisplayFixed { value, decimals }
+}
+
+#[cfg(feature = "fixed")]
+impl<Frac: fixed::types::extra::LeEqU32> core::fmt::Display for DisplayFixed<Frac> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut result = Ok(());
+        write_fixed(self.value.to_bits(), fixed::FixedI32::<Frac>::FRAC_NBITS, self.decimals, |c| {
+            if result.is_ok() {
+                result = f.write_char(c);
+            }
+        });
+        result
+    }
+}
+
+#[cfg(all(feature = "fixed", feature = "ufmt"))]
+impl<Frac: fixed::types::extra::LeEqU32> ufmt::uDisplay for DisplayFixed<Frac> {
+    fn fmt<W: ufmt::uWrite + ?Sized>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error> {
+        let mut buf = [0u8; 4];
+        let mut result = Ok(());
+        write_fixed(self.value.to_bits(), fixed::FixedI32::<Frac>::FRAC_NBITS, self.decimals, |c| {
+            if result.is_ok() {
+                result = f.write_str(c.encode_utf8(&mut buf));
+            }
+        });
+        result
+    }
+}
+
+/// Implementation detail of [`print!`] and [`println!`]: integer types that can be printed without
+/// `core::fmt`.
+#[doc(hidden)]
+pub trait PrintFast {
+    fn __mci_print(&self);
+}
+
+/// Implementation detail of [`print!`] and [`println!`]: the fallback for types that are not
+/// [`PrintFast`]. Implemented on references so that [`PrintFast`] takes priority in method resolution.
+#[doc(hidden)]
+pub trait PrintDisplay {
+    fn __mci_print(&self);
+}
+
+macro_rules! impl_print_fast {
+    ($f:ident as $t:ty: $($int:ty),*) => {
+        $(impl PrintFast for $int {
+            #[inline(always)]
+            fn __mci_print(&self) {
+                $f(*self as $t);
+            }
+        })*
+    };
+}
+
+impl_print_fast!(print_u32 as u32: u8, u16, u32);
+impl_print_fast!(print_i32 as i32: i8, i16, i32);
+impl_print_fast!(print_u64 as u64: u64);
+impl_print_fast!(print_i64 as i64: i64);
+// `usize` and `isize` are 32 bits on wasm32, but may be 64 bits on the host with the simulator
+#[cfg(not(target_pointer_width = "64"))]
+impl_print_fast!(print_u32 as u32: usize);
+#[cfg(not(target_pointer_width = "64"))]
+impl_print_fast!(print_i32 as i32: isize);
+#[cfg(target_pointer_width = "64")]
+impl_print_fast!(print_u64 as u64: usize);
+#[cfg(target_pointer_width = "64")]
+impl_print_fast!(print_i64 as i64: isize);
+
+impl<T: core::fmt::Display + ?Sized> PrintDisplay for &T {
+    #[inline(always)]
+    fn __mci_print(&self) {
+        write!(MciWriteStream, "{}", self).unwrap();
+    }
+}
+
+/// Implementation detail of [`mc_assert!`](crate::mc_assert).
+#[doc(hidden)]
+#[cold]
+pub fn __assert_failed(file: &str, line: u32, expr: &str, msg: Option<core::fmt::Arguments>) -> ! {
+    crate::println!("assertion failed at {}:{}: {}", file, line, expr);
+    if let Some(msg) = msg {
+        crate::println!("  {}", msg);
+    }
+    panic!("assertion failed: {}", expr)
+}
+
+/// Implementation detail of [`mc_assert_eq!`](crate::mc_assert_eq) and
+/// [`mc_assert_ne!`](crate::mc_assert_ne).
+#[doc(hidden)]
+#[cold]
+pub fn __assert_cmp_failed(
+    file: &str,
+    line: u32,
+    op: &str,
+    exprs: (&str, &str),
+    left: &dyn core::fmt::Debug,
+    right: &dyn core::fmt::Debug,
+    msg: Option<core::fmt::Arguments>,
+) -> ! {
+    crate::println!("assertion failed at {}:{}: {} {} {}", file, line, exprs.0, op, exprs.1);
+    crate::println!("   left: {:?}", left);
+    crate::println!("  right: {:?}", right);
+    if let Some(msg) = msg {
+        crate::println!("  {}", msg);
+    }
+    panic!("assertion failed: `left {} right`", op)
+}
+
 /// An implementation of `print!` using [`MciWriteStream`]. Should behave similarly to `std::print!`,
 /// with the caveat that no text will be printed until a newline is printed (due to the fact that
 /// Minecraft has no way of modifying a line of text in the chat once it has been sent), and any characters
 /// that are not printable ASCII characters will appear as �.
+///
+/// `print!("{}", n)` where `n` is an integer uses [`print_i32`] or [`print_u32`] instead of `core::fmt`.
 #[macro_export]
 macro_rules! print {
+    ("{}", $arg:expr $(,)?) => {{
+        #[allow(unused_imports)]
+        use $crate::fmt::{PrintDisplay as _, PrintFast as _};
+        (&$arg).__mci_print();
+    }};
     ($($arg:tt)*) => {{
         use core::fmt::Write;
         write!($crate::fmt::MciWriteStream, $($arg)*).unwrap();
@@ -50,11 +579,187 @@ macro_rules! print {
 
 /// An implementation of `println!` using [`MciWriteStream`]. Should behave similarly to `std::println!`,
 /// with the caveat that any characters that are not printable ASCII characters will appear as �.
+///
+/// `println!("{}", n)` where `n` is an integer uses [`print_i32`] or [`print_u32`] instead of `core::fmt`.
 #[macro_export]
 macro_rules! println {
     () => { mc_putc('\n'); };
+    ("{}", $arg:expr $(,)?) => {{
+        $crate::print!("{}", $arg);
+        $crate::mc_putc('\n');
+    }};
     ($($arg:tt)*) => {{
         use core::fmt::Write;
         writeln!($crate::fmt::MciWriteStream, $($arg)*).unwrap();
     }};
-}
\ No newline at end of file
+}
+
+/// An equivalent of `std::dbg!` that prints to the game chat.
+///
+/// Prints the file and line of the macro invocation, the source text of the expression and its
+/// [`Debug`](core::fmt::Debug) representation, then returns the value of the expression. Unlike
+/// `std::dbg!`, the value is printed with `{:?}` rather than `{:#?}`, since multi-line output is hard
+/// to read in chat. With multiple arguments, each is printed separately and a tuple of the values is
+/// returned.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::mc_dbg;
+/// let a = 2;
+/// let b = mc_dbg!(a * 2) + 1; // prints [src/main.rs:3] a * 2 = 4
+/// ```
+#[macro_export]
+macro_rules! mc_dbg {
+    () => {
+        $crate::println!("[{}:{}]", ::core::file!(), ::core::line!())
+    };
+    ($val:expr $(,)?) => {
+        match $val {
+            tmp => {
+                $crate::println!(
+                    "[{}:{}] {} = {:?}",
+                    ::core::file!(),
+                    ::core::line!(),
+                    ::core::stringify!($val),
+                    &tmp
+                );
+                tmp
+            }
+        }
+    };
+    ($($val:expr),+ $(,)?) => {
+        ($($crate::mc_dbg!($val)),+,)
+    };
+}
+
+/// An implementation of `print!` using [`ufmt`](https://docs.rs/ufmt) and [`MciWriteStream`].
+/// Arguments must implement [`ufmt::uDisplay`] or [`ufmt::uDebug`] rather than the `core::fmt` traits.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::uprint;
+/// uprint!("x = {}, y = {:?}", 1, 2);
+/// ```
+#[cfg(feature = "ufmt")]
+#[macro_export]
+macro_rules! uprint {
+    ($($arg:tt)*) => {{
+        use $crate::fmt::__ufmt as ufmt;
+        let _ = ufmt::uwrite!($crate::fmt::MciWriteStream, $($arg)*);
+    }};
+}
+
+/// An implementation of `println!` using [`ufmt`](https://docs.rs/ufmt) and [`MciWriteStream`].
+/// Arguments must implement [`ufmt::uDisplay`] or [`ufmt::uDebug`] rather than the `core::fmt` traits.
+#[cfg(feature = "ufmt")]
+#[macro_export]
+macro_rules! uprintln {
+    () => { $crate::mc_putc('\n'); };
+    ($($arg:tt)*) => {{
+        use $crate::fmt::__ufmt as ufmt;
+        let _ = ufmt::uwriteln!($crate::fmt::MciWriteStream, $($arg)*);
+    }};
+}
+
+/// Like [`assert!`], but prints the failing expression and its location to the game chat before
+/// panicking. An optional message can be given in the same format as [`println!`].
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::mc_assert;
+/// mc_assert!(health > 0, "player {} died", id);
+/// ```
+#[macro_export]
+macro_rules! mc_assert {
+    ($cond:expr $(,)?) => {
+        if !$cond {
+            $crate::fmt::__assert_failed(
+                ::core::file!(),
+                ::core::line!(),
+                ::core::stringify!($cond),
+                None,
+            );
+        }
+    };
+    ($cond:expr, $($arg:tt)+) => {
+        if !$cond {
+            $crate::fmt::__assert_failed(
+                ::core::file!(),
+                ::core::line!(),
+                ::core::stringify!($cond),
+                Some(::core::format_args!($($arg)+)),
+            );
+        }
+    };
+}
+
+/// Like [`assert_eq!`], but prints both expressions and their [`Debug`](core::fmt::Debug)
+/// representations to the game chat before panicking. An optional message can be given in the same
+/// format as [`println!`].
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::mc_assert_eq;
+/// mc_assert_eq!(turtle_get(), Block::Gold);
+/// ```
+#[macro_export]
+macro_rules! mc_assert_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::__mc_assert_cmp!(==, $left, $right, None)
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        $crate::__mc_assert_cmp!(==, $left, $right, Some(::core::format_args!($($arg)+)))
+    };
+}
+
+/// Like [`assert_ne!`], but prints both expressions and their [`Debug`](core::fmt::Debug)
+/// representations to the game chat before panicking. An optional message can be given in the same
+/// format as [`println!`].
+#[macro_export]
+macro_rules! mc_assert_ne {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::__mc_assert_cmp!(!=, $left, $right, None)
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        $crate::__mc_assert_cmp!(!=, $left, $right, Some(::core::format_args!($($arg)+)))
+    };
+}
+
+/// Implementation detail of [`mc_assert_eq!`] and [`mc_assert_ne!`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __mc_assert_cmp {
+    ($op:tt, $left:expr, $right:expr, $msg:expr) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !(*left $op *right) {
+                    $crate::fmt::__assert_cmp_failed(
+                        ::core::file!(),
+                        ::core::line!(),
+                        ::core::stringify!($op),
+                        (::core::stringify!($left), ::core::stringify!($right)),
+                        left,
+                        right,
+                        $msg,
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[cfg(all(test, feature = "simulator"))]
+mod tests {
+    use crate::simulator;
+    use std::string::ToString;
+
+    #[test]
+    fn prints_pointer_sized_integers() {
+        simulator::reset();
+        crate::println!("{}", usize::MAX);
+        crate::println!("{}", isize::MIN);
+        crate::println!("{}", isize::MAX);
+        let expected = [usize::MAX.to_string(), isize::MIN.to_string(), isize::MAX.to_string()];
+        simulator::with_world(|world| assert_eq!(world.chat(), expected));
+    }
+}
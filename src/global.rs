@@ -0,0 +1,93 @@
+//! Global state that belongs to the running program.
+//!
+//! wasmcraft2 runs a single thread, so the crate's global state could be plain `static mut`s. With
+//! the `simulator` feature, however, every thread has its own [`World`](crate::simulator::World) so
+//! that tests can run in parallel, and the rest of the crate's state has to be per-thread as well.
+//! [`global!`] declares a [`Global`], which is a plain static when compiling for wasmcraft2 and a
+//! thread-local under the simulator.
+
+#[cfg(feature = "simulator")]
+use core::cell::RefCell;
+#[cfg(not(feature = "simulator"))]
+use core::cell::UnsafeCell;
+#[cfg(feature = "simulator")]
+use std::thread::LocalKey;
+
+/// A global value, declared with [`global!`].
+pub(crate) struct Global<T: 'static> {
+    #[cfg(not(feature = "simulator"))]
+    value: UnsafeCell<T>,
+    #[cfg(feature = "simulator")]
+    key: &'static LocalKey<RefCell<T>>,
+}
+
+// SAFETY: wasmcraft2 programs are single-threaded, so the value is never accessed from two threads
+#[cfg(not(feature = "simulator"))]
+unsafe impl<T> Sync for Global<T> {}
+
+impl<T> Global<T> {
+    #[cfg(not(feature = "simulator"))]
+    #[doc(hidden)]
+    pub(crate) const fn new(value: T) -> Self {
+        Self { value: UnsafeCell::new(value) }
+    }
+
+    #[cfg(feature = "simulator")]
+    #[doc(hidden)]
+    pub(crate) const fn new(key: &'static LocalKey<RefCell<T>>) -> Self {
+        Self { key }
+    }
+
+    /// Run a function with mutable access to the value. The function must not access the same
+    /// global again.
+    #[inline(always)]
+    pub(crate) fn with<R>(&'static self, f: impl FnOnce(&mut T) -> R) -> R {
+        #[cfg(not(feature = "simulator"))]
+        // SAFETY: there is only one thread, and `f` does not access this global again, so this is the
+        // only reference to the value
+        return f(unsafe { &mut *self.value.get() });
+        #[cfg(feature = "simulator")]
+        return self.key.with(|value| f(&mut value.borrow_mut()));
+    }
+
+    /// Get a copy of the value.
+    #[inline(always)]
+    pub(crate) fn get(&'static self) -> T
+    where
+        T: Copy,
+    {
+        self.with(|value| *value)
+    }
+
+    /// Replace the value, returning the old one.
+    #[inline(always)]
+    pub(crate) fn replace(&'static self, value: T) -> T {
+        self.with(|old| core::mem::replace(old, value))
+    }
+
+    /// Set the value.
+    #[inline(always)]
+    pub(crate) fn set(&'static self, value: T) {
+        self.replace(value);
+    }
+}
+
+/// Declare a [`Global`] static, initialised with a constant expression.
+macro_rules! global {
+    ($(#[$attr:meta])* static $name:ident: $ty:ty = $init:expr;) => {
+        $(#[$attr])*
+        #[cfg(not(feature = "simulator"))]
+        static $name: $crate::global::Global<$ty> = $crate::global::Global::new($init);
+
+        $(#[$attr])*
+        #[cfg(feature = "simulator")]
+        static $name: $crate::global::Global<$ty> = {
+            std::thread_local! {
+                static VALUE: core::cell::RefCell<$ty> = const { core::cell::RefCell::new($init) };
+            }
+            $crate::global::Global::new(&VALUE)
+        };
+    };
+}
+
+pub(crate) use global;
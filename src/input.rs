@@ -0,0 +1,200 @@
+//! Reading player input from the world.
+//!
+//! wasmcraft2 programs have no direct way of receiving input from players, but they can read blocks.
+//! [`Input`] treats designated block positions as buttons: a player places a block at the position to
+//! press the button, and removes it to release it. Calling [`Input::poll`] once per tick reads every
+//! button and reports which ones changed, with debouncing so that a single press is not reported more
+//! than once.
+
+use crate::{turtle_get, turtle_pos, Block};
+
+/// A position registered with [`Input::register`].
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+struct Button {
+    name: &'static str,
+    pos: (i32, i32, i32),
+    /// The debounced block at the position.
+    block: Block,
+    /// The block most recently read at the position, which becomes the debounced block once it has
+    /// been read enough times in a row.
+    candidate: Block,
+    stable_polls: u8,
+    changed: bool,
+    initialised: bool,
+}
+
+impl Button {
+    const EMPTY: Button = Button {
+        name: "",
+        pos: (0, 0, 0),
+        block: Block::Air,
+        candidate: Block::Air,
+        stable_polls: 0,
+        changed: false,
+        initialised: false,
+    };
+}
+
+/// A change in the state of a button, reported by [`Input::changes`].
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Event {
+    /// The ID returned by [`Input::register`].
+    pub id: usize,
+    /// The name given to [`Input::register`].
+    pub name: &'static str,
+    /// The block now at the button's position.
+    pub block: Block,
+}
+
+impl Event {
+    /// Whether the button is now pressed (i.e. there is a block other than air at its position).
+    #[inline(always)]
+    pub fn pressed(&self) -> bool {
+        self.block != Block::Air
+    }
+}
+
+/// A set of up to `N` buttons at fixed positions in the world.
+///
+/// A button is pressed when there is any block other than [`Block::Air`] at its position. A change is
+/// only reported once the new block has been read by `debounce` consecutive calls to
+/// [`Input::poll`], which filters out blocks that are only present briefly.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{input::Input, mc_sleep, println};
+/// let mut input: Input<4> = Input::new(2);
+/// let jump = input.register("jump", 10, 64, 10).unwrap();
+/// input.register("reset", 12, 64, 10).unwrap();
+/// loop {
+///     input.poll();
+///     if input.just_pressed(jump) {
+///         // ...
+///     }
+///     for event in input.changes() {
+///         println!("{} {}", event.name, if event.pressed() { "pressed" } else { "released" });
+///     }
+///     mc_sleep();
+/// }
+/// ```
+pub struct Input<const N: usize> {
+    buttons: [Button; N],
+    len: usize,
+    debounce: u8,
+}
+
+impl<const N: usize> Input<N> {
+    /// Create a new set of buttons. A change is reported once it has been seen by `debounce`
+    /// consecutive polls; a value of 0 or 1 reports changes as soon as they are seen.
+    pub const fn new(debounce: u8) -> Self {
+        Self {
+            buttons: [Button::EMPTY; N],
+            len: 0,
+            debounce,
+        }
+    }
+
+    /// Register a button at the given position, returning its ID, or `None` if `N` buttons have
+    /// already been registered.
+    ///
+    /// The initial state of the button is read on the next call to [`Input::poll`], without
+    /// reporting a change.
+    pub fn register(&mut self, name: &'static str, x: i32, y: i32, z: i32) -> Option<usize> {
+        let button = self.buttons.get_mut(self.len)?;
+        *button = Button {
+            name,
+            pos: (x, y, z),
+            ..Button::EMPTY
+        };
+        self.len += 1;
+        Some(self.len - 1)
+    }
+
+    /// Get the ID of the first button registered with the given name.
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.buttons[..self.len].iter().position(|b| b.name == name)
+    }
+
+    /// The number of registered buttons.
+    #[allow(clippy::len_without_is_empty)]
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Read every button, updating their states. Returns the number of buttons that changed.
+    ///
+    /// This should be called once per tick. The first poll after a button is registered only records
+    /// its initial state without reporting a change.
+    ///
+    /// This moves the turtle, so the turtle position is undefined after calling this function.
+    pub fn poll(&mut self) -> usize {
+        let mut changes = 0;
+        for button in self.buttons[..self.len].iter_mut() {
+            turtle_pos(button.pos.0, button.pos.1, button.pos.2);
+            let block = turtle_get();
+            button.changed = false;
+            if !button.initialised {
+                button.block = block;
+                button.candidate = block;
+                button.initialised = true;
+                continue;
+            }
+
+            if block != button.candidate {
+                button.candidate = block;
+                button.stable_polls = 0;
+            }
+            button.stable_polls = button.stable_polls.saturating_add(1);
+            if button.candidate != button.block && button.stable_polls >= self.debounce {
+                button.block = button.candidate;
+                button.changed = true;
+                changes += 1;
+            }
+        }
+        changes
+    }
+
+    /// Iterate over the buttons that changed during the last call to [`Input::poll`].
+    pub fn changes(&self) -> impl Iterator<Item = Event> + '_ {
+        self.buttons[..self.len]
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.changed)
+            .map(|(id, b)| Event {
+                id,
+                name: b.name,
+                block: b.block,
+            })
+    }
+
+    /// The debounced block at a button's position.
+    #[inline]
+    pub fn block(&self, id: usize) -> Block {
+        self.buttons[..self.len]
+            .get(id)
+            .map_or(Block::Air, |b| b.block)
+    }
+
+    /// Whether a button is currently pressed.
+    #[inline]
+    pub fn is_pressed(&self, id: usize) -> bool {
+        self.block(id) != Block::Air
+    }
+
+    /// Whether a button was pressed during the last call to [`Input::poll`].
+    #[inline]
+    pub fn just_pressed(&self, id: usize) -> bool {
+        self.buttons[..self.len]
+            .get(id)
+            .is_some_and(|b| b.changed && b.block != Block::Air)
+    }
+
+    /// Whether a button was released during the last call to [`Input::poll`].
+    #[inline]
+    pub fn just_released(&self, id: usize) -> bool {
+        self.buttons[..self.len]
+            .get(id)
+            .is_some_and(|b| b.changed && b.block == Block::Air)
+    }
+}
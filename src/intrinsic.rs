@@ -0,0 +1,77 @@
+//! Declaring additional wasmcraft2 intrinsics.
+//!
+//! This crate wraps every function in wasmcraft2's `mcinterface.h`, but patched or forked builds of
+//! wasmcraft2 can provide extra imports. [`declare_intrinsic!`](crate::declare_intrinsic) generates
+//! the `extern` declaration and a safe, inlined wrapper for each one, in the same way as the
+//! functions in the crate root.
+//!
+//! When not compiling for WebAssembly (e.g. when testing with the `simulator` feature), the imports
+//! do not exist, so the wrapper runs a fallback body instead, or panics if none was given.
+
+/// Declare safe wrappers for extra intrinsics provided by a patched wasmcraft2.
+///
+/// Each function is declared like an ordinary function signature followed by `=` and the name of the
+/// import, and optionally `else` and a block to run instead when not compiling for WebAssembly.
+/// Without a fallback block, calling the function off WebAssembly panics.
+///
+/// The wrappers are safe functions, so only declare intrinsics that are safe to call with any
+/// arguments, and only use types that wasmcraft2 can pass (integers and `#[repr(C)]` enums such as
+/// [`Block`](crate::Block)).
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::declare_intrinsic;
+/// declare_intrinsic! {
+///     /// Rotate the turtle by the given number of quarter turns.
+///     pub fn turtle_rotate(quarter_turns: i32) = "turtle_rotate";
+///
+///     /// Get the current game time.
+///     pub fn game_time() -> i32 = "game_time" else { 0 };
+/// }
+///
+/// turtle_rotate(1);
+/// ```
+#[macro_export]
+macro_rules! declare_intrinsic {
+    ($(
+        $(#[$attr:meta])*
+        $vis:vis fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)? = $link:literal
+            $(else $fallback:block)?;
+    )*) => {$(
+        $(#[$attr])*
+        #[cfg(target_arch = "wasm32")]
+        #[inline(always)]
+        $vis fn $name($($arg: $ty),*) $(-> $ret)? {
+            extern "C" {
+                #[link_name = $link]
+                fn intrinsic($($arg: $ty),*) $(-> $ret)?;
+            }
+            unsafe { intrinsic($($arg),*) }
+        }
+
+        $(#[$attr])*
+        #[cfg(not(target_arch = "wasm32"))]
+        #[allow(unused_variables)]
+        #[inline(always)]
+        $vis fn $name($($arg: $ty),*) $(-> $ret)? {
+            $crate::__intrinsic_fallback!($link $($fallback)?)
+        }
+    )*};
+}
+
+/// The body of an intrinsic wrapper when not compiling for WebAssembly. Used by
+/// [`declare_intrinsic!`](crate::declare_intrinsic).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __intrinsic_fallback {
+    ($link:literal) => {
+        ::core::panic!(::core::concat!(
+            "the `",
+            $link,
+            "` intrinsic is only available when running in wasmcraft2"
+        ))
+    };
+    ($link:literal $fallback:block) => {
+        $fallback
+    };
+}
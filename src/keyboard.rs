@@ -0,0 +1,140 @@
+//! Text input from blocks.
+//!
+//! Contains [`BlockKeyboard`], which reads a strip of block positions using an
+//! [`Input`](crate::input::Input) and decodes the pattern of blocks placed there into characters.
+//!
+//! A player enters a character by placing the blocks for it in the strip, waiting for it to be read,
+//! and then clearing the strip again before entering the next character.
+
+use crate::input::Input;
+use crate::Block;
+
+/// How patterns of blocks are converted into characters.
+#[derive(Copy, Clone, Debug)]
+pub enum KeyMap<'a, const K: usize> {
+    /// Each pattern in the table produces the corresponding character. Patterns not in the table are
+    /// ignored.
+    Table(&'a [([Block; K], char)]),
+    /// The strip is read as a base 14 number, with the first block being the most significant digit
+    /// and each block's position in the [`Block`] enum as its value (so [`Block::Air`] is 0). A value
+    /// `n` between 1 and 95 produces the ASCII character `31 + n`, so with two blocks every printable
+    /// ASCII character can be entered.
+    Base14,
+}
+
+impl<const K: usize> KeyMap<'_, K> {
+    /// Decode a non-empty pattern of blocks.
+    fn decode(&self, pattern: &[Block; K]) -> Option<char> {
+        match self {
+            KeyMap::Table(table) => table.iter().find(|(p, _)| p == pattern).map(|(_, c)| *c),
+            KeyMap::Base14 => {
+                let mut value: u32 = 0;
+                for block in pattern {
+                    value = value.checked_mul(14)?.checked_add(*block as u32)?;
+                }
+                match value {
+                    1..=95 => char::from_u32(31 + value),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+/// The number of decoded characters that can be waiting to be read.
+const QUEUE_LEN: usize = 16;
+
+/// A keyboard made of a strip of `K` block positions along the x axis.
+///
+/// Every tick, [`BlockKeyboard::poll`] reads the strip. Once a pattern has been unchanged for `settle`
+/// polls, it is decoded using the [`KeyMap`] and the resulting character is queued to be returned by
+/// [`BlockKeyboard::read_char`]. The strip must then be cleared (filled with air) before another
+/// character is accepted, so holding a pattern does not repeat it.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{keyboard::{BlockKeyboard, KeyMap}, mc_putc, mc_sleep};
+/// let mut keyboard: BlockKeyboard<2> = BlockKeyboard::new((0, 64, 0), 20, KeyMap::Base14);
+/// loop {
+///     keyboard.poll();
+///     while let Some(c) = keyboard.read_char() {
+///         mc_putc(c);
+///     }
+///     mc_sleep();
+/// }
+/// ```
+pub struct BlockKeyboard<'a, const K: usize> {
+    input: Input<K>,
+    map: KeyMap<'a, K>,
+    settle: u8,
+    stable_polls: u8,
+    armed: bool,
+    queue: [char; QUEUE_LEN],
+    queue_start: usize,
+    queue_len: usize,
+}
+
+impl<'a, const K: usize> BlockKeyboard<'a, K> {
+    /// Create a keyboard whose strip starts at `origin` and extends `K` blocks towards positive x.
+    /// A pattern is decoded once it has been unchanged for `settle` polls.
+    pub fn new(origin: (i32, i32, i32), settle: u8, map: KeyMap<'a, K>) -> Self {
+        let mut input = Input::new(1);
+        for i in 0..K {
+            input.register("key", origin.0 + i as i32, origin.1, origin.2);
+        }
+        Self {
+            input,
+            map,
+            settle,
+            stable_polls: 0,
+            armed: false,
+            queue: ['\0'; QUEUE_LEN],
+            queue_start: 0,
+            queue_len: 0,
+        }
+    }
+
+    /// Read the strip, queueing a character if a complete pattern has been entered. This should be
+    /// called once per tick.
+    ///
+    /// If the queue of unread characters is full, new characters are dropped.
+    ///
+    /// This moves the turtle, so the turtle position is undefined after calling this function.
+    pub fn poll(&mut self) {
+        let changes = self.input.poll();
+        let mut pattern = [Block::Air; K];
+        for (i, block) in pattern.iter_mut().enumerate() {
+            *block = self.input.block(i);
+        }
+
+        if pattern.iter().all(|b| *b == Block::Air) {
+            self.armed = true;
+            return;
+        }
+        self.stable_polls = if changes > 0 {
+            0
+        } else {
+            self.stable_polls.saturating_add(1)
+        };
+        if self.armed && self.stable_polls >= self.settle {
+            self.armed = false;
+            if let Some(c) = self.map.decode(&pattern) {
+                if self.queue_len < QUEUE_LEN {
+                    self.queue[(self.queue_start + self.queue_len) % QUEUE_LEN] = c;
+                    self.queue_len += 1;
+                }
+            }
+        }
+    }
+
+    /// Get the next character that has been entered, if there is one.
+    pub fn read_char(&mut self) -> Option<char> {
+        if self.queue_len == 0 {
+            return None;
+        }
+        let c = self.queue[self.queue_start];
+        self.queue_start = (self.queue_start + 1) % QUEUE_LEN;
+        self.queue_len -= 1;
+        Some(c)
+    }
+}
@@ -6,8 +6,11 @@
 //!
 //! When writing programs for wasmcraft2, it is important to note its limitations - notably, floating
 //! point operations are not supported, so using the [`fixed`](https://docs.rs/fixed/latest/fixed/)
-//! crate is recommended if integers are not enough. Minecraft programs must be `#![no_main]` and `#![no_std]`; this
-//! crate provides a Minecraft-compatible panic handler but there is no allocator. Decreasing the default
+//! crate is recommended if integers are not enough (the `fixed` feature adds integer-only printing for
+//! its types, see [`fmt::print_fixed`]), and the `soft-float` feature provides a software floating point
+//! type, `math::float::F32`, for porting code written with `f32`. Minecraft programs must be `#![no_main]` and `#![no_std]`; this
+//! crate provides a Minecraft-compatible panic handler but there is no allocator unless the `alloc` feature is
+//! enabled (see the [`allocator`] module). Decreasing the default
 //! stack size is recommended - you can do this by adding the following to your `.cargo/config`:
 //! ```toml
 //! [target.wasm32-unknown-unknown]
@@ -34,13 +37,144 @@
 //!     return 0;
 //! }
 //! ```
+//! If the entrypoint is not declared exactly like this, the datapack will do nothing when run. With
+//! the `main` feature enabled, the [`main`] attribute can be used to generate it instead.
+//!
+//! Rebuilding a datapack and loading it in Minecraft is slow, so the `simulator` feature can be used
+//! to run programs on the host against an in-memory world instead (see the [`simulator`] module).
 
 #![no_std]
 
+#[cfg(feature = "simulator")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+pub mod allocator;
+pub mod animation;
+pub mod batch;
+pub mod blockmem;
+#[cfg(feature = "fmt")]
+pub mod chat_ui;
+pub mod chart;
+pub mod checkpoint;
+pub mod collections;
+pub mod console;
+#[cfg(feature = "fmt")]
+pub mod dump;
+pub mod flood;
 #[cfg(feature = "fmt")]
 pub mod fmt;
+mod global;
+pub mod input;
+pub mod intrinsic;
+pub mod keyboard;
+#[cfg(feature = "fmt")]
+pub mod logging;
+pub mod math;
+pub mod mem;
+pub mod pacing;
+pub mod panic;
+pub mod pod;
+#[cfg(feature = "fmt")]
+pub mod profile;
+pub mod rand;
+pub mod region;
+pub mod rle;
+pub mod scan;
+pub mod scheduler;
+pub mod segment;
+pub mod schematic;
+pub mod screen;
+#[cfg(feature = "simulator")]
+pub mod simulator;
+pub mod sprite;
+#[cfg(target_arch = "wasm32")]
+pub mod stack;
+#[cfg(feature = "fmt")]
+pub mod style;
+pub mod task;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod text;
+pub mod transform;
+pub mod vec;
+
+/// Turn a function into the wasmcraft2 entrypoint.
+///
+/// The function must take no arguments and return `i32`, `!` or nothing (which returns `0`). This
+/// generates the `#[no_mangle] pub extern "C" fn _start() -> i32` that wasmcraft2 requires, which
+/// calls the function after running any setup given in the attribute's arguments, in this order:
+/// - `stack`: call [`stack::init`] so that stack usage can be monitored.
+/// - `log`: call [`logging::init`] to install the [`log`](https://docs.rs/log) crate backend
+///   (requires the `log` feature).
+/// - `init = <function>`: call the given function, e.g. to register [`input`] buttons.
+///
+/// The global allocator (with the `alloc` feature) needs no setup, since its arena is static.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::println;
+/// #[mcinterface::main(stack, init = setup)]
+/// fn main() -> i32 {
+///     println("Hello, world!");
+///     0
+/// }
+///
+/// fn setup() {
+///     // ...
+/// }
+/// ```
+#[cfg(feature = "main")]
+pub use mcinterface_macros::main;
+
+/// Mark a function as a test to be run in-game by [`mc_test_main!`].
+///
+/// The function must take no arguments and return nothing. It fails if it panics, or with
+/// `#[mc_test(should_panic)]`, if it does not panic. Forgetting to list it in [`mc_test_main!`] is a
+/// compile error. See the [`testing`] module for details.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{mc_test, mc_assert};
+/// #[mc_test]
+/// fn arithmetic_works() {
+///     mc_assert!(2 + 2 == 4);
+/// }
+/// ```
+#[cfg(feature = "testing")]
+pub use mcinterface_macros::mc_test;
+
+/// Embed an image file as a [`Sprite`](sprite::Sprite) at compile time.
+///
+/// The path is relative to the directory containing your crate's `Cargo.toml`. PNG and BMP images
+/// are supported. Each pixel is converted to the [`Block`] closest to it in colour, and pixels that
+/// are mostly transparent become [`Block::Air`].
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{include_image, sprite::Sprite};
+/// const LOGO: Sprite<32, 16> = include_image!("assets/logo.png");
+/// LOGO.draw();
+/// ```
+#[cfg(feature = "image")]
+pub use mcinterface_macros::include_image;
 
-use core::panic::PanicInfo;
+/// Embed a structure file as a [`Schematic`](schematic::Schematic) at compile time.
+///
+/// The path is relative to the directory containing your crate's `Cargo.toml`. Sponge schematics
+/// (`.schem`), vanilla structure block files (`.nbt`) and Litematica schematics (`.litematic`) are
+/// supported. Only the blocks supported by wasmcraft2 (see [`Block`]) can be placed; by default any
+/// other block causes a compile error, but a fallback block to use instead can be given.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{include_schematic, schematic::Schematic, turtle_pos, Block};
+/// const HOUSE: Schematic = include_schematic!("structures/house.schem", fallback = Block::Cobblestone);
+/// turtle_pos(0, 64, 0);
+/// HOUSE.paste_at_turtle();
+/// ```
+#[cfg(feature = "schematic")]
+pub use mcinterface_macros::include_schematic;
 
 /// An enum representing a Minecraft block.
 /// This contains all the block types currently supported by wasmcraft2, which is a very limited
@@ -65,13 +199,137 @@ pub enum Block {
     OakLeaves,
 }
 
-extern {
+impl Block {
+    /// Every block, in order of their numeric IDs.
+    pub const ALL: [Block; 14] = [
+        Block::Air,
+        Block::Cobblestone,
+        Block::Granite,
+        Block::Andesite,
+        Block::Diorite,
+        Block::Lapis,
+        Block::Iron,
+        Block::Gold,
+        Block::Diamond,
+        Block::Redstone,
+        Block::Emerald,
+        Block::Dirt,
+        Block::OakLog,
+        Block::OakLeaves,
+    ];
+
+    /// Get the block with the given numeric ID (its position in the enum), if there is one.
+    ///
+    /// This is the same as [`Block::try_from`], but can be used in constants.
+    pub const fn from_id(id: i32) -> Option<Block> {
+        Some(match id {
+            0 => Block::Air,
+            1 => Block::Cobblestone,
+            2 => Block::Granite,
+            3 => Block::Andesite,
+            4 => Block::Diorite,
+            5 => Block::Lapis,
+            6 => Block::Iron,
+            7 => Block::Gold,
+            8 => Block::Diamond,
+            9 => Block::Redstone,
+            10 => Block::Emerald,
+            11 => Block::Dirt,
+            12 => Block::OakLog,
+            13 => Block::OakLeaves,
+            _ => return None,
+        })
+    }
+
+    /// Get the block with the given numeric ID without checking it.
+    ///
+    /// This skips the check done by [`Block::try_from`], for IDs that are already known to be valid,
+    /// e.g. because they were produced by [`Block::as_i32`]. Blocks added by newer versions of
+    /// wasmcraft2 cannot be represented this way; they need to be added to this enum.
+    ///
+    /// # Safety
+    /// `id` must be the ID of one of the variants (see [`Block::ALL`]). Any other value is undefined
+    /// behaviour.
+    #[inline(always)]
+    pub const unsafe fn from_raw_unchecked(id: i32) -> Block {
+        core::mem::transmute::<i32, Block>(id)
+    }
+
+    /// The numeric ID of the block (its position in the enum), as used by wasmcraft2.
+    #[inline(always)]
+    pub const fn as_i32(self) -> i32 {
+        self as i32
+    }
+
+    /// Whether the block is [`Block::Air`].
+    #[inline(always)]
+    pub const fn is_air(self) -> bool {
+        matches!(self, Block::Air)
+    }
+
+    /// Whether the block is solid, i.e. any block other than [`Block::Air`].
+    #[inline(always)]
+    pub const fn is_solid(self) -> bool {
+        !self.is_air()
+    }
+
+    /// Whether the block can be seen through: [`Block::Air`] and [`Block::OakLeaves`].
+    #[inline(always)]
+    pub const fn is_transparent(self) -> bool {
+        matches!(self, Block::Air | Block::OakLeaves)
+    }
+
+    /// Whether the block is a mineral block (the blocks that ores are smelted into).
+    #[inline(always)]
+    pub const fn is_mineral(self) -> bool {
+        matches!(
+            self,
+            Block::Lapis | Block::Iron | Block::Gold | Block::Diamond | Block::Redstone | Block::Emerald
+        )
+    }
+}
+
+/// The error returned when converting an integer that is not a valid block ID to a [`Block`].
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct InvalidBlockId(pub i32);
+
+impl core::fmt::Display for InvalidBlockId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "invalid block ID {}", self.0)
+    }
+}
+
+impl TryFrom<i32> for Block {
+    type Error = InvalidBlockId;
+
+    #[inline]
+    fn try_from(id: i32) -> Result<Block, InvalidBlockId> {
+        Block::from_id(id).ok_or(InvalidBlockId(id))
+    }
+}
+
+impl From<Block> for i32 {
+    #[inline(always)]
+    fn from(block: Block) -> i32 {
+        block.as_i32()
+    }
+}
+
+#[cfg(feature = "simulator")]
+use simulator::ffi::*;
+#[cfg(feature = "simulator")]
+pub use simulator::ffi::memset;
+
+#[cfg(not(feature = "simulator"))]
+extern "C" {
     #[link_name = "print"]
     fn _mci_unsafe_print(value: i32);
 
     /// Set all bytes in a region of memory (with length `length`, starting from `ptr`) to `value`.
     ///
-    /// As far as I can tell, behaves like C `memset()`.
+    /// As far as I can tell, behaves like C `memset()`: `length` is in bytes and only the lowest 8
+    /// bits of `value` are used, despite the `*mut i32` pointer. The [`mem`] module has safe
+    /// wrappers.
     pub fn memset(ptr: *mut i32, value: i32, length: u32) -> *mut i32;
 
     #[link_name = "turtle_x"]
@@ -107,33 +365,78 @@ pub fn print(value: i32) {
     unsafe { _mci_unsafe_print(value) }
 }
 
+/// The last turtle position set through this crate. wasmcraft2 has no way of reading the turtle
+/// position, so it is tracked here. The simulator tracks it in the [`simulator::World`] instead.
+#[cfg(not(feature = "simulator"))]
+static mut TURTLE_POSITION: (i32, i32, i32) = (0, 0, 0);
+
 /// Set the x position of the turtle
 #[inline(always)]
 pub fn turtle_x(value: i32) {
-    unsafe { _mci_unsafe_turtle_x(value) }
+    unsafe {
+        #[cfg(not(feature = "simulator"))]
+        {
+            TURTLE_POSITION.0 = value;
+        }
+        _mci_unsafe_turtle_x(value)
+    }
 }
 
 /// Set the y position of the turtle.
 #[inline(always)]
 pub fn turtle_y(value: i32) {
-    unsafe { _mci_unsafe_turtle_y(value) }
+    unsafe {
+        #[cfg(not(feature = "simulator"))]
+        {
+            TURTLE_POSITION.1 = value;
+        }
+        _mci_unsafe_turtle_y(value)
+    }
 }
 
 /// Set the z position of the turtle.
 #[inline(always)]
 pub fn turtle_z(value: i32) {
-    unsafe { _mci_unsafe_turtle_z(value) }
+    unsafe {
+        #[cfg(not(feature = "simulator"))]
+        {
+            TURTLE_POSITION.2 = value;
+        }
+        _mci_unsafe_turtle_z(value)
+    }
 }
 
 /// Set the position of the turtle. This will call `turtle_x`, `turtle_y` and `turtle_z`, so it is
 /// more efficient to call those individually if you do not need to change all 3 coordinates.
 #[inline(always)]
 pub fn turtle_pos(x: i32, y: i32, z: i32) {
-    unsafe {
-        _mci_unsafe_turtle_x(x);
-        _mci_unsafe_turtle_y(y);
-        _mci_unsafe_turtle_z(z);
-    }
+    turtle_x(x);
+    turtle_y(y);
+    turtle_z(z);
+}
+
+/// Get the position of the turtle as `(x, y, z)`.
+///
+/// wasmcraft2 provides no way of reading the turtle position, so this returns the last position set
+/// through this crate, or `(0, 0, 0)` if the turtle has not been moved yet.
+#[inline(always)]
+pub fn turtle_position() -> (i32, i32, i32) {
+    #[cfg(feature = "simulator")]
+    return simulator::with_world(|world| world.turtle());
+    #[cfg(not(feature = "simulator"))]
+    unsafe { TURTLE_POSITION }
+}
+
+/// Set the position of the turtle to a [`vec::IVec3`]. See [`turtle_pos`].
+#[inline(always)]
+pub fn turtle_pos_vec(pos: vec::IVec3) {
+    turtle_pos(pos.x, pos.y, pos.z);
+}
+
+/// Get the position of the turtle as a [`vec::IVec3`]. See [`turtle_position`].
+#[inline(always)]
+pub fn turtle_position_vec() -> vec::IVec3 {
+    turtle_position().into()
 }
 
 
@@ -141,6 +444,8 @@ pub fn turtle_pos(x: i32, y: i32, z: i32) {
 /// The x, y, and z span arguments are effectively the size of the region minus one,
 /// so `turtle_fill(block, 0, 0, 0)` is equivalent to `turtle_set(block)`
 ///
+/// To fill a [`Region`](region::Region), use [`Region::fill`](region::Region::fill).
+///
 /// This function is unstable, and may cause wasmcraft2 to fail compilation.
 #[inline(always)]
 pub fn turtle_fill(block: Block, x_span: i32, y_span: i32, z_span: i32) {
@@ -167,7 +472,8 @@ pub fn turtle_check(block: Block) -> bool {
 
 /// Copy a given region from the turtle's position.
 ///
-/// Paste the region using [`turtle_paste_region_masked()`].
+/// Paste the region using [`turtle_paste_region_masked()`]. To copy a [`Region`](region::Region),
+/// use [`Region::copy`](region::Region::copy).
 #[inline(always)]
 pub fn turtle_copy_region(x_span: i32, y_span: i32, z_span: i32) {
     unsafe { _mci_unsafe_turtle_copy_region(x_span, y_span, z_span); }
@@ -202,9 +508,38 @@ pub fn turtle_paste() {
 /// wasmcraft2 will automatically insert sleep calls before functions and inside loops. However, if
 /// your program contains large stretches of code without loops or function calls, it may be necessary
 /// to manually insert `mc_sleep()` calls. See the wasmcraft2 README for more information.
+///
+/// Each call increments the counter returned by [`ticks()`].
 #[inline(always)]
 pub fn mc_sleep() {
-    unsafe { _mci_unsafe_mc_sleep() }
+    unsafe {
+        #[cfg(not(feature = "simulator"))]
+        {
+            TICKS = TICKS.wrapping_add(1);
+        }
+        _mci_unsafe_mc_sleep()
+    }
+}
+
+/// The number of times [`mc_sleep()`] has been called. The simulator counts them in the
+/// [`simulator::World`] instead.
+#[cfg(not(feature = "simulator"))]
+static mut TICKS: u32 = 0;
+
+/// Get the number of game ticks that have passed, counted by calls to [`mc_sleep()`] (including those
+/// made by this crate, e.g. by [`task::block_on`]). Wraps around on overflow.
+///
+/// Sleeps inserted automatically by wasmcraft2 are not counted, so this is a lower bound on the
+/// number of ticks that have actually passed. To time part of a program, see the [`profile`] module.
+///
+/// With the `simulator` feature, this is the tick counter of the current thread's world (see
+/// [`simulator::World::ticks`]), so it is reset by [`simulator::reset`].
+#[inline(always)]
+pub fn ticks() -> u32 {
+    #[cfg(feature = "simulator")]
+    return simulator::with_world(|world| world.ticks() as u32);
+    #[cfg(not(feature = "simulator"))]
+    unsafe { TICKS }
 }
 
 /// Write a character to the game chat. Characters will not appear until a newline (`'\n'`) is written.
@@ -256,8 +591,47 @@ macro_rules! env_i32_default {
     }};
 }
 
-#[panic_handler]
-fn panic(_info: &PanicInfo) -> ! {
-    println("RUST PANIC - entering infinite loop!");
-    loop { mc_sleep(); }
-}
\ No newline at end of file
+/// Declare a structure as a grid of characters, evaluated at compile time.
+///
+/// With a single list of rows, this produces a horizontal layer `[[Block; W]; D]`, where each row
+/// (a string literal) runs along the x axis and successive rows go towards positive z. With several
+/// bracketed lists of rows, this produces a 3D structure `[[[Block; W]; D]; H]` with layers listed
+/// from the bottom up. After the `;`, each character used in the grid is assigned a block; keys can
+/// be identifiers or character literals.
+///
+/// Use [`schematic::build_layer`] and [`schematic::build`] to place the result at the turtle
+/// position.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{blocks, Block, schematic};
+/// const FLOOR: [[Block; 3]; 3] = blocks! {
+///     "X.X"
+///     "XXX"
+///     "X.X"
+///     ; X = Block::Iron, '.' = Block::Air
+/// };
+/// const PILLAR: [[[Block; 1]; 1]; 3] = blocks! { ["L"] ["L"] ["O"] ; L = Block::OakLog, O = Block::OakLeaves };
+/// schematic::build_layer(&FLOOR);
+/// schematic::build(&PILLAR);
+/// ```
+#[macro_export]
+macro_rules! blocks {
+    ($([$($row:literal)+])+ ; $($key:tt = $block:expr),+ $(,)?) => {{
+        const KEYS: &[(u8, $crate::Block)] = &[$(($crate::schematic::__blocks_key(stringify!($key)), $block)),+];
+        const LAYERS: &[&[&str]] = &[$(&[$($row),+]),+];
+        const W: usize = LAYERS[0][0].len();
+        const D: usize = LAYERS[0].len();
+        const H: usize = LAYERS.len();
+        const STRUCTURE: [[[$crate::Block; W]; D]; H] = $crate::schematic::__blocks_layers(LAYERS, KEYS);
+        STRUCTURE
+    }};
+    ($($row:literal)+ ; $($key:tt = $block:expr),+ $(,)?) => {{
+        const KEYS: &[(u8, $crate::Block)] = &[$(($crate::schematic::__blocks_key(stringify!($key)), $block)),+];
+        const ROWS: &[&str] = &[$($row),+];
+        const W: usize = ROWS[0].len();
+        const D: usize = ROWS.len();
+        const LAYER: [[$crate::Block; W]; D] = $crate::schematic::__blocks_layer(ROWS, KEYS);
+        LAYER
+    }};
+}
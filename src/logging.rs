@@ -0,0 +1,212 @@
+//! Leveled logging to the game chat.
+//!
+//! The [`mc_error!`](crate::mc_error), [`mc_warn!`](crate::mc_warn), [`mc_info!`](crate::mc_info),
+//! [`mc_debug!`](crate::mc_debug) and [`mc_trace!`](crate::mc_trace) macros work like
+//! [`println!`](crate::println), but prefix each message with its level and the module it was logged
+//! from.
+//!
+//! The most verbose level that is printed is chosen at compile time with the `max-level-off`,
+//! `max-level-error`, `max-level-warn`, `max-level-info`, `max-level-debug` and `max-level-trace`
+//! features (if more than one is enabled, the least verbose wins). Messages above the maximum level
+//! are removed entirely by the compiler, so they cost no commands. Without any of these features,
+//! every level is printed.
+//!
+//! With the `log` feature enabled, [`init`] installs [`MciLogger`] as the logger for the
+//! [`log`](https://docs.rs/log) crate, so libraries using its macros also log to the game chat, in
+//! the same format and subject to the same maximum level.
+
+use crate::fmt::MciWriteStream;
+use crate::style::{Color, Style};
+use core::fmt::Write;
+
+/// The level of a log message, from least to most verbose.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Debug, Hash)]
+#[repr(usize)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    /// The name of the level as printed before log messages, e.g. `"WARN"`.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+
+    /// The colour of the level in log messages, if formatting codes are enabled (see
+    /// [`style::set_enabled`](crate::style::set_enabled)).
+    pub const fn color(&self) -> Color {
+        match self {
+            Level::Error => Color::Red,
+            Level::Warn => Color::Gold,
+            Level::Info => Color::Green,
+            Level::Debug => Color::Aqua,
+            Level::Trace => Color::Gray,
+        }
+    }
+}
+
+/// The most verbose level that will be printed, selected by the `max-level-*` features. `0` means
+/// that nothing is printed.
+pub const MAX_LEVEL: usize = if cfg!(feature = "max-level-off") {
+    0
+} else if cfg!(feature = "max-level-error") {
+    Level::Error as usize
+} else if cfg!(feature = "max-level-warn") {
+    Level::Warn as usize
+} else if cfg!(feature = "max-level-info") {
+    Level::Info as usize
+} else if cfg!(feature = "max-level-debug") {
+    Level::Debug as usize
+} else {
+    Level::Trace as usize
+};
+
+/// Whether messages at the given level are printed.
+#[inline(always)]
+pub const fn enabled(level: Level) -> bool {
+    level as usize <= MAX_LEVEL
+}
+
+/// Implementation detail of [`mc_log!`](crate::mc_log).
+#[doc(hidden)]
+pub fn __log(level: Level, module: &str, args: core::fmt::Arguments) {
+    crate::mc_putc('[');
+    Style::new().color(level.color()).print(level.as_str());
+    crate::mc_putc(' ');
+    crate::print_str(module);
+    crate::print_str("] ");
+    let _ = MciWriteStream.write_fmt(args);
+    crate::mc_putc('\n');
+}
+
+#[cfg(feature = "log")]
+impl From<log::Level> for Level {
+    fn from(level: log::Level) -> Self {
+        match level {
+            log::Level::Error => Level::Error,
+            log::Level::Warn => Level::Warn,
+            log::Level::Info => Level::Info,
+            log::Level::Debug => Level::Debug,
+            log::Level::Trace => Level::Trace,
+        }
+    }
+}
+
+/// A [`log::Log`] implementation that prints to the game chat in the same format as
+/// [`mc_log!`](crate::mc_log), using the record's target in place of the module path.
+///
+/// Use [`init`] to install it.
+#[cfg(feature = "log")]
+pub struct MciLogger;
+
+#[cfg(feature = "log")]
+impl log::Log for MciLogger {
+    #[inline]
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        enabled(metadata.level().into())
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            __log(record.level().into(), record.target(), *record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Install [`MciLogger`] as the logger for the `log` crate, with its maximum level set to
+/// [`MAX_LEVEL`]. Returns an error if a logger has already been installed.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::logging;
+/// logging::init().unwrap();
+/// log::info!("hello from the log crate");
+/// ```
+#[cfg(feature = "log")]
+pub fn init() -> Result<(), log::SetLoggerError> {
+    static LOGGER: MciLogger = MciLogger;
+    log::set_logger(&LOGGER)?;
+    log::set_max_level(match MAX_LEVEL {
+        0 => log::LevelFilter::Off,
+        1 => log::LevelFilter::Error,
+        2 => log::LevelFilter::Warn,
+        3 => log::LevelFilter::Info,
+        4 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    });
+    Ok(())
+}
+
+/// Log a message at the given [`Level`], if that level is enabled.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{mc_log, logging::Level};
+/// mc_log!(Level::Info, "loaded {} chunks", 4);
+/// ```
+#[macro_export]
+macro_rules! mc_log {
+    ($level:expr, $($arg:tt)+) => {{
+        let level: $crate::logging::Level = $level;
+        if $crate::logging::enabled(level) {
+            $crate::logging::__log(level, ::core::module_path!(), ::core::format_args!($($arg)+));
+        }
+    }};
+}
+
+/// Log a message at the [`Error`](crate::logging::Level::Error) level. Uses the same syntax as
+/// [`println!`](crate::println).
+#[macro_export]
+macro_rules! mc_error {
+    ($($arg:tt)+) => {
+        $crate::mc_log!($crate::logging::Level::Error, $($arg)+)
+    };
+}
+
+/// Log a message at the [`Warn`](crate::logging::Level::Warn) level. Uses the same syntax as
+/// [`println!`](crate::println).
+#[macro_export]
+macro_rules! mc_warn {
+    ($($arg:tt)+) => {
+        $crate::mc_log!($crate::logging::Level::Warn, $($arg)+)
+    };
+}
+
+/// Log a message at the [`Info`](crate::logging::Level::Info) level. Uses the same syntax as
+/// [`println!`](crate::println).
+#[macro_export]
+macro_rules! mc_info {
+    ($($arg:tt)+) => {
+        $crate::mc_log!($crate::logging::Level::Info, $($arg)+)
+    };
+}
+
+/// Log a message at the [`Debug`](crate::logging::Level::Debug) level. Uses the same syntax as
+/// [`println!`](crate::println).
+#[macro_export]
+macro_rules! mc_debug {
+    ($($arg:tt)+) => {
+        $crate::mc_log!($crate::logging::Level::Debug, $($arg)+)
+    };
+}
+
+/// Log a message at the [`Trace`](crate::logging::Level::Trace) level. Uses the same syntax as
+/// [`println!`](crate::println).
+#[macro_export]
+macro_rules! mc_trace {
+    ($($arg:tt)+) => {
+        $crate::mc_log!($crate::logging::Level::Trace, $($arg)+)
+    };
+}
@@ -0,0 +1,314 @@
+//! Integer and fixed-point maths.
+//!
+//! wasmcraft2 does not support floating point operations, so everything in this module uses integer
+//! arithmetic only. Division is slow in wasmcraft2, so where possible these functions avoid it.
+//! Fixed-point numbers are `i32`s with 16 fractional bits, so [`ONE`] represents 1.
+//!
+//! Minecraft's scoreboard division and remainder round towards negative infinity, while WebAssembly
+//! (like Rust) rounds towards zero, so wasmcraft2 has to correct the results of signed division, and
+//! its handling of edge cases (negative operands, `i32::MIN`, division by zero) is not always what
+//! Rust code expects. [`checked_div`], [`checked_rem`] and the related functions in this module have
+//! the same results as the methods of the same name on `i32`, but only ever divide non-negative
+//! numbers, so they give those results regardless of how wasmcraft2 lowers signed division.
+
+#[cfg(feature = "soft-float")]
+pub mod float;
+pub mod noise;
+pub mod trig;
+
+/// The fixed-point representation of 1 used by this module.
+pub const ONE: i32 = 1 << 16;
+
+/// The integer square root of `n`, rounded down.
+pub const fn isqrt(n: u32) -> u32 {
+    let mut n = n;
+    let mut root = 0;
+    let mut bit = 1 << 30;
+    while bit > n {
+        bit >>= 2;
+    }
+    while bit != 0 {
+        if n >= root + bit {
+            n -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+    root
+}
+
+/// The integer cube root of `n`, rounded towards zero.
+pub const fn icbrt(n: i32) -> i32 {
+    let mut x = n.unsigned_abs();
+    let mut root: u32 = 0;
+    let mut shift = 30;
+    loop {
+        root *= 2;
+        let b = 3 * root * (root + 1) + 1;
+        if x >> shift >= b {
+            x -= b << shift;
+            root += 1;
+        }
+        if shift == 0 {
+            break;
+        }
+        shift -= 3;
+    }
+    if n < 0 {
+        -(root as i32)
+    } else {
+        root as i32
+    }
+}
+
+/// The greatest common divisor of `a` and `b`, or 0 if both are 0. Uses the binary GCD algorithm,
+/// which needs no division.
+pub const fn gcd(a: u32, b: u32) -> u32 {
+    if a == 0 {
+        return b;
+    }
+    if b == 0 {
+        return a;
+    }
+    let (mut a, mut b) = (a, b);
+    let shift = (a | b).trailing_zeros();
+    a >>= a.trailing_zeros();
+    while b != 0 {
+        b >>= b.trailing_zeros();
+        if a > b {
+            (a, b) = (b, a);
+        }
+        b -= a;
+    }
+    a << shift
+}
+
+/// Linearly interpolate between `a` and `b` by the fixed-point fraction `t` (so a `t` of 0 gives `a`
+/// and a `t` of [`ONE`] gives `b`), rounding down. `t` may be outside of that range to extrapolate.
+#[inline]
+pub const fn lerp(a: i32, b: i32, t: i32) -> i32 {
+    (a as i64 + (((b as i64 - a as i64) * t as i64) >> 16)) as i32
+}
+
+/// Restrict `value` to the range `min..=max`. Unlike [`Ord::clamp`], this can be used in constants,
+/// and does not panic if `min > max` (the result is `max` in that case).
+#[inline]
+pub const fn clamp(value: i32, min: i32, max: i32) -> i32 {
+    let value = if value < min { min } else { value };
+    if value > max {
+        max
+    } else {
+        value
+    }
+}
+
+/// `|a| / |b|` for `a` and `b` up to 2^31, dividing only non-negative `i32`s. `b` must not be 0.
+const fn div_abs(a: u32, b: u32) -> u32 {
+    const MAX: u32 = i32::MAX as u32;
+    if b > MAX {
+        // b is 2^31, which is at least as large as a
+        (a == b) as u32
+    } else if a > MAX {
+        // a is 2^31; a - b fits in an i32 and is non-negative
+        ((a - b) as i32 / b as i32) as u32 + 1
+    } else {
+        (a as i32 / b as i32) as u32
+    }
+}
+
+/// Divide `a` by `b`, rounding towards zero, or return `None` if `b` is 0 or the result overflows
+/// (`i32::MIN / -1`). Same as [`i32::checked_div`].
+pub const fn checked_div(a: i32, b: i32) -> Option<i32> {
+    if b == 0 || (a == i32::MIN && b == -1) {
+        return None;
+    }
+    let quotient = div_abs(a.unsigned_abs(), b.unsigned_abs());
+    Some(if (a < 0) != (b < 0) {
+        (quotient as i32).wrapping_neg()
+    } else {
+        quotient as i32
+    })
+}
+
+/// The remainder of dividing `a` by `b`, rounding towards zero (so the result has the same sign as
+/// `a`), or `None` if `b` is 0 or the division overflows (`i32::MIN % -1`). Same as
+/// [`i32::checked_rem`].
+pub const fn checked_rem(a: i32, b: i32) -> Option<i32> {
+    match checked_div(a, b) {
+        Some(quotient) => Some(a.wrapping_sub(quotient.wrapping_mul(b))),
+        None => None,
+    }
+}
+
+/// Divide `a` by `b`, rounding so that the remainder is non-negative (see
+/// [`checked_rem_euclid`]), or return `None` if `b` is 0 or the result overflows. Same as
+/// [`i32::checked_div_euclid`].
+///
+/// For a positive `b` this rounds towards negative infinity, which makes it suitable for converting
+/// block coordinates to chunk or grid coordinates.
+pub const fn checked_div_euclid(a: i32, b: i32) -> Option<i32> {
+    match checked_div(a, b) {
+        Some(quotient) if a.wrapping_sub(quotient.wrapping_mul(b)) < 0 => {
+            Some(if b > 0 { quotient - 1 } else { quotient + 1 })
+        }
+        result => result,
+    }
+}
+
+/// The non-negative remainder of dividing `a` by `b`, or `None` if `b` is 0 or the division
+/// overflows. Same as [`i32::checked_rem_euclid`].
+pub const fn checked_rem_euclid(a: i32, b: i32) -> Option<i32> {
+    match checked_rem(a, b) {
+        Some(rem) if rem < 0 => Some(rem.wrapping_add(b.wrapping_abs())),
+        result => result,
+    }
+}
+
+/// Divide `a` by `b`, rounding towards zero, wrapping on overflow (so `i32::MIN / -1` is
+/// `i32::MIN`). Same as [`i32::wrapping_div`].
+///
+/// # Panics
+/// Panics if `b` is 0.
+pub const fn wrapping_div(a: i32, b: i32) -> i32 {
+    match checked_div(a, b) {
+        Some(quotient) => quotient,
+        None if b == 0 => panic!("attempt to divide by zero"),
+        None => i32::MIN,
+    }
+}
+
+/// The remainder of dividing `a` by `b`, rounding towards zero, wrapping on overflow (so
+/// `i32::MIN % -1` is 0). Same as [`i32::wrapping_rem`].
+///
+/// # Panics
+/// Panics if `b` is 0.
+pub const fn wrapping_rem(a: i32, b: i32) -> i32 {
+    match checked_rem(a, b) {
+        Some(rem) => rem,
+        None if b == 0 => panic!("attempt to calculate the remainder with a divisor of zero"),
+        None => 0,
+    }
+}
+
+/// Shift `a` left by `shift` bits, or return `None` if `shift` is 32 or more. Same as
+/// [`i32::checked_shl`], except that the shift is never masked, so the result does not depend on
+/// whether wasmcraft2 masks shift amounts.
+pub const fn checked_shl(a: i32, shift: u32) -> Option<i32> {
+    if shift < 32 {
+        Some(a << shift)
+    } else {
+        None
+    }
+}
+
+/// Shift `a` right by `shift` bits, keeping its sign, or return `None` if `shift` is 32 or more. Same
+/// as [`i32::checked_shr`], except that the shift is never masked, so the result does not depend on
+/// whether wasmcraft2 masks shift amounts.
+pub const fn checked_shr(a: i32, shift: u32) -> Option<i32> {
+    if shift < 32 {
+        Some(a >> shift)
+    } else {
+        None
+    }
+}
+
+#[cfg(all(test, feature = "simulator"))]
+mod tests {
+    use super::*;
+
+    /// Operands covering zero, ±1, both signs and the extremes.
+    const VALUES: [i32; 12] = [0, 1, -1, 2, -2, 7, -7, 100, -100, i32::MAX, i32::MIN, i32::MIN + 1];
+
+    #[test]
+    fn division_overflow() {
+        assert_eq!(checked_div(i32::MIN, -1), None);
+        assert_eq!(checked_rem(i32::MIN, -1), None);
+        assert_eq!(checked_div_euclid(i32::MIN, -1), None);
+        assert_eq!(checked_rem_euclid(i32::MIN, -1), None);
+        assert_eq!(wrapping_div(i32::MIN, -1), i32::MIN);
+        assert_eq!(wrapping_rem(i32::MIN, -1), 0);
+    }
+
+    #[test]
+    fn division_by_zero() {
+        for a in VALUES {
+            assert_eq!(checked_div(a, 0), None);
+            assert_eq!(checked_rem(a, 0), None);
+            assert_eq!(checked_div_euclid(a, 0), None);
+            assert_eq!(checked_rem_euclid(a, 0), None);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn wrapping_div_by_zero() {
+        wrapping_div(1, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "divisor of zero")]
+    fn wrapping_rem_by_zero() {
+        wrapping_rem(1, 0);
+    }
+
+    #[test]
+    fn truncating_division() {
+        assert_eq!(checked_div(-7, 2), Some(-3));
+        assert_eq!(checked_div(7, -2), Some(-3));
+        assert_eq!(checked_div(-7, -2), Some(3));
+        assert_eq!(checked_rem(-7, 2), Some(-1));
+        assert_eq!(checked_rem(7, -2), Some(1));
+        assert_eq!(checked_rem(-7, -2), Some(-1));
+        assert_eq!(checked_div(i32::MIN, 2), Some(-(1 << 30)));
+        assert_eq!(checked_div(i32::MIN, i32::MIN), Some(1));
+        assert_eq!(checked_div(i32::MAX, i32::MIN), Some(0));
+        for a in VALUES {
+            for b in VALUES {
+                assert_eq!(checked_div(a, b), a.checked_div(b), "{a} / {b}");
+                assert_eq!(checked_rem(a, b), a.checked_rem(b), "{a} % {b}");
+                if b != 0 {
+                    assert_eq!(wrapping_div(a, b), a.wrapping_div(b), "{a} / {b}");
+                    assert_eq!(wrapping_rem(a, b), a.wrapping_rem(b), "{a} % {b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn euclidean_division() {
+        assert_eq!(checked_div_euclid(-7, 2), Some(-4));
+        assert_eq!(checked_div_euclid(7, -2), Some(-3));
+        assert_eq!(checked_div_euclid(-7, -2), Some(4));
+        assert_eq!(checked_rem_euclid(-7, 2), Some(1));
+        assert_eq!(checked_rem_euclid(7, -2), Some(1));
+        assert_eq!(checked_rem_euclid(-7, -2), Some(1));
+        assert_eq!(checked_div_euclid(-1, 16), Some(-1));
+        assert_eq!(checked_rem_euclid(i32::MIN, i32::MAX), Some(i32::MAX - 1));
+        for a in VALUES {
+            for b in VALUES {
+                assert_eq!(checked_div_euclid(a, b), a.checked_div_euclid(b), "{a} div_euclid {b}");
+                assert_eq!(checked_rem_euclid(a, b), a.checked_rem_euclid(b), "{a} rem_euclid {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn shifts() {
+        assert_eq!(checked_shl(1, 31), Some(i32::MIN));
+        assert_eq!(checked_shl(-1, 31), Some(i32::MIN));
+        assert_eq!(checked_shr(i32::MIN, 31), Some(-1));
+        assert_eq!(checked_shr(i32::MAX, 31), Some(0));
+        for shift in [32, 33, 63, 64, u32::MAX] {
+            assert_eq!(checked_shl(1, shift), None);
+            assert_eq!(checked_shr(-1, shift), None);
+        }
+        for a in VALUES {
+            for shift in [0, 1, 16, 31, 32, 33, 100] {
+                assert_eq!(checked_shl(a, shift), a.checked_shl(shift), "{a} << {shift}");
+                assert_eq!(checked_shr(a, shift), a.checked_shr(shift), "{a} >> {shift}");
+            }
+        }
+    }
+}
@@ -0,0 +1,529 @@
+//! A software floating point number type.
+//!
+//! wasmcraft2 cannot compile floating point instructions, so code using `f32` cannot be run in
+//! Minecraft. [`F32`] is a replacement implemented entirely with integer arithmetic, with the usual
+//! arithmetic operators, so that numeric code can be ported by changing its types rather than
+//! rewriting every expression in fixed-point.
+//!
+//! [`F32`] is not an IEEE 754 float: it has a 31-bit mantissa (more precise than `f32`) and a 32-bit
+//! exponent (so it practically never overflows), but no infinities, NaN or negative zero, and
+//! dividing by zero panics. Since it has no NaN, it implements [`Eq`] and [`Ord`].
+//!
+//! Usage:
+//! ```ignore
+//! # use mcinterface::math::float::F32;
+//! let radius = F32::from(12);
+//! let area = F32::PI * radius * radius;
+//! let half = F32::from_ratio(1, 2);
+//! mcinterface::println!("{:.2}", area * half); // 226.19
+//! ```
+
+use core::cmp::Ordering;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A software floating point number, equal to `mantissa * 2^exponent`.
+///
+/// The mantissa is normalised so that its magnitude is at least 2^30 and less than 2^31, except for
+/// zero, which has a mantissa and exponent of 0. This means every value has exactly one
+/// representation.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Default, Hash)]
+pub struct F32 {
+    mantissa: i32,
+    exponent: i32,
+}
+
+impl F32 {
+    /// 0.
+    pub const ZERO: F32 = F32 { mantissa: 0, exponent: 0 };
+    /// 1.
+    pub const ONE: F32 = F32 { mantissa: 1 << 30, exponent: -30 };
+    /// 0.5.
+    pub const HALF: F32 = F32 { mantissa: 1 << 30, exponent: -31 };
+    /// Archimedes' constant (π).
+    pub const PI: F32 = F32 { mantissa: 1686629713, exponent: -29 };
+    /// Euler's number (e).
+    pub const E: F32 = F32 { mantissa: 1459366444, exponent: -29 };
+
+    /// Create a number from a mantissa and exponent of any size, rounding to the nearest
+    /// representable value.
+    const fn normalize(mantissa: i64, exponent: i32) -> F32 {
+        if mantissa == 0 {
+            return F32::ZERO;
+        }
+        let magnitude = mantissa.unsigned_abs();
+        // Shift so that the highest set bit of the magnitude is bit 30
+        let shift = 33 - magnitude.leading_zeros() as i32;
+        let (mut magnitude, mut exponent) = if shift > 0 {
+            let half = 1 << (shift - 1);
+            ((magnitude + half) >> shift, exponent.saturating_add(shift))
+        } else {
+            (magnitude << -shift, exponent.saturating_add(shift))
+        };
+        // Rounding up may have carried into bit 31
+        if magnitude >> 31 != 0 {
+            magnitude >>= 1;
+            exponent = exponent.saturating_add(1);
+        }
+        let mantissa = if mantissa < 0 {
+            -(magnitude as i32)
+        } else {
+            magnitude as i32
+        };
+        F32 { mantissa, exponent }
+    }
+
+    /// Create a number equal to `mantissa * 2^exponent`.
+    #[inline]
+    pub const fn from_parts(mantissa: i32, exponent: i32) -> F32 {
+        F32::normalize(mantissa as i64, exponent)
+    }
+
+    /// Create a number from an integer.
+    #[inline]
+    pub const fn from_i32(value: i32) -> F32 {
+        F32::normalize(value as i64, 0)
+    }
+
+    /// Create a number equal to `numerator / denominator`.
+    ///
+    /// # Panics
+    /// Panics if `denominator` is 0.
+    pub fn from_ratio(numerator: i32, denominator: i32) -> F32 {
+        F32::from_i32(numerator) / F32::from_i32(denominator)
+    }
+
+    /// Create a number from a fixed-point number with `frac_bits` fractional bits, e.g. one from the
+    /// [`math`](crate::math) module (which has 16) or the [`fixed`](https://docs.rs/fixed) crate.
+    #[inline]
+    pub const fn from_fixed(bits: i32, frac_bits: u32) -> F32 {
+        F32::normalize(bits as i64, -(frac_bits as i32))
+    }
+
+    /// Convert to a fixed-point number with `frac_bits` fractional bits, rounding towards negative
+    /// infinity and saturating if it does not fit.
+    pub const fn to_fixed(self, frac_bits: u32) -> i32 {
+        let exponent = self.exponent.saturating_add(frac_bits as i32);
+        if self.mantissa == 0 || exponent <= -32 {
+            return if self.mantissa < 0 { -1 } else { 0 };
+        }
+        if exponent >= 32 {
+            return if self.mantissa < 0 { i32::MIN } else { i32::MAX };
+        }
+        let value = if exponent >= 0 {
+            (self.mantissa as i64) << exponent
+        } else {
+            (self.mantissa as i64) >> -exponent
+        };
+        if value > i32::MAX as i64 {
+            i32::MAX
+        } else if value < i32::MIN as i64 {
+            i32::MIN
+        } else {
+            value as i32
+        }
+    }
+
+    /// Convert to an integer, rounding towards zero and saturating if it does not fit, like
+    /// `f32 as i32`.
+    #[inline]
+    pub const fn to_i32(self) -> i32 {
+        self.trunc().to_fixed(0)
+    }
+
+    /// Create a number from the bits of an IEEE 754 `f32`, e.g. from `f32::to_bits` in a constant.
+    /// Infinities and NaN become [`F32::ZERO`].
+    ///
+    /// Usage:
+    /// ```ignore
+    /// # use mcinterface::math::float::F32;
+    /// const GRAVITY: F32 = F32::from_ieee_bits(9.81f32.to_bits());
+    /// ```
+    pub const fn from_ieee_bits(bits: u32) -> F32 {
+        let biased = ((bits >> 23) & 0xff) as i32;
+        let fraction = (bits & 0x7f_ffff) as i64;
+        let mantissa = match biased {
+            0 => fraction,
+            0xff => 0,
+            _ => fraction | 1 << 23,
+        };
+        let mantissa = if bits >> 31 != 0 { -mantissa } else { mantissa };
+        F32::normalize(mantissa, if biased == 0 { -149 } else { biased - 150 })
+    }
+
+    /// Convert to the bits of the nearest IEEE 754 `f32`, e.g. for printing or sending to a host.
+    /// Values too large for an `f32` become infinities, and values too small become zero.
+    pub const fn to_ieee_bits(self) -> u32 {
+        if self.mantissa == 0 {
+            return 0;
+        }
+        let sign = if self.mantissa < 0 { 1 << 31 } else { 0 };
+        let magnitude = self.mantissa.unsigned_abs();
+        // The value is 1.x * 2^(exponent + 30)
+        let biased = self.exponent.saturating_add(30 + 127);
+        if biased >= 0xff {
+            return sign | 0x7f80_0000;
+        }
+        if biased <= 0 {
+            // Subnormal: the value is fraction * 2^-149
+            let shift = 7 + 1 - biased;
+            if shift >= 32 {
+                return sign;
+            }
+            return sign | ((magnitude + (1 << (shift - 1))) >> shift);
+        }
+        // Round the 31-bit mantissa to 24 bits; a carry correctly increments the exponent
+        let rounded = (magnitude + (1 << 6)) >> 7;
+        sign | (((biased as u32) << 23) + (rounded - (1 << 23)))
+    }
+
+    /// Whether the number is 0.
+    #[inline]
+    pub const fn is_zero(self) -> bool {
+        self.mantissa == 0
+    }
+
+    /// Whether the number is less than 0.
+    #[inline]
+    pub const fn is_negative(self) -> bool {
+        self.mantissa < 0
+    }
+
+    /// Whether the number is greater than 0.
+    #[inline]
+    pub const fn is_positive(self) -> bool {
+        self.mantissa > 0
+    }
+
+    /// The absolute value of the number.
+    #[inline]
+    pub const fn abs(self) -> F32 {
+        F32 { mantissa: self.mantissa.abs(), exponent: self.exponent }
+    }
+
+    /// `1` if the number is positive, `-1` if it is negative, or `0` if it is 0.
+    #[inline]
+    pub const fn signum(self) -> F32 {
+        F32::from_i32(self.mantissa.signum())
+    }
+
+    /// The largest integer less than or equal to the number.
+    pub const fn floor(self) -> F32 {
+        if self.exponent >= 0 {
+            return self;
+        }
+        if self.exponent <= -31 {
+            return if self.mantissa < 0 { F32::from_i32(-1) } else { F32::ZERO };
+        }
+        let mask = (1 << -self.exponent) - 1;
+        F32::from_parts(self.mantissa & !mask, self.exponent)
+    }
+
+    /// The smallest integer greater than or equal to the number.
+    #[inline]
+    pub const fn ceil(self) -> F32 {
+        self.neg_const().floor().neg_const()
+    }
+
+    /// The integer part of the number, rounding towards zero.
+    #[inline]
+    pub const fn trunc(self) -> F32 {
+        if self.mantissa < 0 {
+            self.ceil()
+        } else {
+            self.floor()
+        }
+    }
+
+    /// The nearest integer to the number, rounding half-way cases away from zero.
+    pub fn round(self) -> F32 {
+        if self.is_negative() {
+            -(-self + F32::HALF).floor()
+        } else {
+            (self + F32::HALF).floor()
+        }
+    }
+
+    /// The fractional part of the number, `self - self.trunc()`.
+    #[inline]
+    pub fn fract(self) -> F32 {
+        self - self.trunc()
+    }
+
+    /// The square root of the number.
+    ///
+    /// # Panics
+    /// Panics if the number is negative.
+    pub fn sqrt(self) -> F32 {
+        if self.mantissa < 0 {
+            panic!("square root of a negative number");
+        }
+        if self.mantissa == 0 {
+            return F32::ZERO;
+        }
+        // Shift the mantissa up to bit 61 or 62, so that the exponent is even and the square root
+        // has 31 bits
+        let (shift, exponent) = if self.exponent % 2 == 0 {
+            (32, self.exponent - 32)
+        } else {
+            (31, self.exponent - 31)
+        };
+        let root = isqrt_u64((self.mantissa as u64) << shift);
+        F32::normalize(root as i64, exponent / 2)
+    }
+
+    /// The smaller of two numbers.
+    #[inline]
+    pub fn min(self, other: F32) -> F32 {
+        Ord::min(self, other)
+    }
+
+    /// The larger of two numbers.
+    #[inline]
+    pub fn max(self, other: F32) -> F32 {
+        Ord::max(self, other)
+    }
+
+    /// Multiply the number by 2^`n`.
+    #[inline]
+    pub const fn mul_pow2(self, n: i32) -> F32 {
+        if self.mantissa == 0 {
+            return self;
+        }
+        F32 { mantissa: self.mantissa, exponent: self.exponent.saturating_add(n) }
+    }
+
+    #[inline]
+    const fn neg_const(self) -> F32 {
+        F32 { mantissa: -self.mantissa, exponent: self.exponent }
+    }
+}
+
+/// The integer square root of `n`, rounded down.
+const fn isqrt_u64(n: u64) -> u64 {
+    let mut n = n;
+    let mut root = 0;
+    let mut bit = 1 << 62;
+    while bit > n {
+        bit >>= 2;
+    }
+    while bit != 0 {
+        if n >= root + bit {
+            n -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+    root
+}
+
+impl Ord for F32 {
+    fn cmp(&self, other: &F32) -> Ordering {
+        let sign = self.mantissa.signum().cmp(&other.mantissa.signum());
+        if sign != Ordering::Equal || self.mantissa == 0 {
+            return sign;
+        }
+        let magnitude = self
+            .exponent
+            .cmp(&other.exponent)
+            .then(self.mantissa.unsigned_abs().cmp(&other.mantissa.unsigned_abs()));
+        if self.mantissa < 0 {
+            magnitude.reverse()
+        } else {
+            magnitude
+        }
+    }
+}
+
+impl PartialOrd for F32 {
+    #[inline]
+    fn partial_cmp(&self, other: &F32) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<i32> for F32 {
+    #[inline]
+    fn from(value: i32) -> F32 {
+        F32::from_i32(value)
+    }
+}
+
+impl From<i16> for F32 {
+    #[inline]
+    fn from(value: i16) -> F32 {
+        F32::from_i32(value as i32)
+    }
+}
+
+impl From<u16> for F32 {
+    #[inline]
+    fn from(value: u16) -> F32 {
+        F32::from_i32(value as i32)
+    }
+}
+
+impl From<i8> for F32 {
+    #[inline]
+    fn from(value: i8) -> F32 {
+        F32::from_i32(value as i32)
+    }
+}
+
+impl From<u8> for F32 {
+    #[inline]
+    fn from(value: u8) -> F32 {
+        F32::from_i32(value as i32)
+    }
+}
+
+impl From<u32> for F32 {
+    #[inline]
+    fn from(value: u32) -> F32 {
+        F32::normalize(value as i64, 0)
+    }
+}
+
+impl From<F32> for i32 {
+    #[inline]
+    fn from(value: F32) -> i32 {
+        value.to_i32()
+    }
+}
+
+impl Neg for F32 {
+    type Output = F32;
+
+    #[inline]
+    fn neg(self) -> F32 {
+        self.neg_const()
+    }
+}
+
+impl Add for F32 {
+    type Output = F32;
+
+    fn add(self, rhs: F32) -> F32 {
+        if self.mantissa == 0 {
+            return rhs;
+        }
+        if rhs.mantissa == 0 {
+            return self;
+        }
+        let (big, small) = if self.exponent >= rhs.exponent {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+        // Keep 30 extra bits of the smaller number, which is plenty for rounding
+        let difference = (big.exponent as i64 - small.exponent as i64).min(62) as u32;
+        let big_mantissa = (big.mantissa as i64) << 30;
+        let small_mantissa = ((small.mantissa as i64) << 30) >> difference;
+        F32::normalize(big_mantissa + small_mantissa, big.exponent.saturating_sub(30))
+    }
+}
+
+impl Sub for F32 {
+    type Output = F32;
+
+    #[inline]
+    fn sub(self, rhs: F32) -> F32 {
+        self + -rhs
+    }
+}
+
+impl Mul for F32 {
+    type Output = F32;
+
+    fn mul(self, rhs: F32) -> F32 {
+        if self.mantissa == 0 || rhs.mantissa == 0 {
+            return F32::ZERO;
+        }
+        F32::normalize(
+            self.mantissa as i64 * rhs.mantissa as i64,
+            self.exponent.saturating_add(rhs.exponent),
+        )
+    }
+}
+
+impl Div for F32 {
+    type Output = F32;
+
+    /// # Panics
+    /// Panics if `rhs` is 0.
+    fn div(self, rhs: F32) -> F32 {
+        if rhs.mantissa == 0 {
+            panic!("attempt to divide by zero");
+        }
+        if self.mantissa == 0 {
+            return F32::ZERO;
+        }
+        // The quotient of the mantissas has 31 or 32 bits
+        F32::normalize(
+            ((self.mantissa as i64) << 32) / rhs.mantissa as i64,
+            self.exponent.saturating_sub(rhs.exponent).saturating_sub(32),
+        )
+    }
+}
+
+macro_rules! impl_assign {
+    ($($trait:ident $method:ident $op:tt),*) => {$(
+        impl $trait for F32 {
+            #[inline]
+            fn $method(&mut self, rhs: F32) {
+                *self = *self $op rhs;
+            }
+        }
+    )*};
+}
+
+impl_assign!(AddAssign add_assign +, SubAssign sub_assign -, MulAssign mul_assign *, DivAssign div_assign /);
+
+impl core::iter::Sum for F32 {
+    fn sum<I: Iterator<Item = F32>>(iter: I) -> F32 {
+        iter.fold(F32::ZERO, Add::add)
+    }
+}
+
+impl core::iter::Product for F32 {
+    fn product<I: Iterator<Item = F32>>(iter: I) -> F32 {
+        iter.fold(F32::ONE, Mul::mul)
+    }
+}
+
+impl core::fmt::Display for F32 {
+    /// Prints the number in decimal, with the given precision (6 decimal places by default).
+    /// Numbers too large to print in decimal (about 2^62 and above) are printed as
+    /// `mantissa*2^exponent`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use core::fmt::Write;
+
+        let decimals = f.precision().unwrap_or(6).min(9);
+        if self.exponent > 31 {
+            return write!(f, "{}*2^{}", self.mantissa, self.exponent);
+        }
+        let scale = 10u64.pow(decimals as u32);
+        // The magnitude in fixed point with 32 fractional bits, rounded to the requested precision
+        let magnitude = self.abs();
+        let half = F32::HALF / F32::from(scale as u32);
+        let fixed = magnitude + half;
+        let whole = fixed.floor();
+        let fraction = fixed - whole;
+        let whole = if whole.exponent >= 0 {
+            (whole.mantissa as u64) << whole.exponent
+        } else {
+            (whole.mantissa as u64) >> -whole.exponent
+        };
+        let digits = (fraction * F32::from(scale as u32)).floor().to_fixed(0) as u64;
+
+        if self.is_negative() {
+            f.write_char('-')?;
+        }
+        write!(f, "{}", whole)?;
+        if decimals > 0 {
+            write!(f, ".{:01$}", digits.min(scale - 1), decimals)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+//! Value and gradient (Perlin) noise on fixed-point numbers.
+//!
+//! Noise functions give smoothly varying random values, which are the usual starting point for
+//! generating terrain and textures. Coordinates and results are fixed-point numbers with 16
+//! fractional bits (see [`ONE`]): the noise has features about one unit apart, so to get hills about
+//! 32 blocks across, pass block coordinates multiplied by `ONE / 32`. Results are in the range
+//! `-ONE..=ONE`. Each `seed` gives a different, unrelated noise field.
+//!
+//! [`noise2`] and [`noise3`] are Perlin's gradient noise, which looks more natural; [`value2`] and
+//! [`value3`] are value noise, which is cheaper but blockier. [`fbm2`] and [`fbm3`] add several
+//! octaves of gradient noise together ("fractal Brownian motion") to give detail at several scales.
+//!
+//! There are no lookup tables, so these use no memory, and the only multiplications are by
+//! constants or between fixed-point numbers.
+//!
+//! Usage:
+//! ```ignore
+//! # use mcinterface::{math::{noise::fbm2, ONE}, turtle_fill, turtle_pos, Block};
+//! // Generate a 64x64 patch of hills
+//! for x in 0..64 {
+//!     for z in 0..64 {
+//!         let height = 8 + fbm2(x * (ONE / 32), z * (ONE / 32), 1234, 4) * 8 / ONE;
+//!         turtle_pos(x, 64, z);
+//!         turtle_fill(Block::Dirt, 0, height, 0);
+//!     }
+//! }
+//! ```
+
+use super::{clamp, lerp, ONE};
+
+/// Hash a lattice point into 32 random-looking bits.
+#[inline]
+fn hash(seed: u32, x: i32, y: i32, z: i32) -> u32 {
+    let mut h = seed
+        ^ (x as u32).wrapping_mul(0x27d4_eb2d)
+        ^ (y as u32).wrapping_mul(0x1656_67b1)
+        ^ (z as u32).wrapping_mul(0x9e37_79b1);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x2c1b_3c6d);
+    h ^= h >> 12;
+    h = h.wrapping_mul(0x297a_2d39);
+    h ^ (h >> 15)
+}
+
+/// Perlin's fade curve `6t^5 - 15t^4 + 10t^3`, for `t` in `0..ONE`.
+#[inline]
+fn fade(t: i32) -> i32 {
+    let t = t as i64;
+    let t3 = (((t * t) >> 16) * t) >> 16;
+    let inner = ((t * (6 * t - (15 << 16))) >> 16) + (10 << 16);
+    ((t3 * inner) >> 16) as i32
+}
+
+/// Split a fixed-point coordinate into its lattice cell and the fraction within it.
+#[inline]
+fn split(coord: i32) -> (i32, i32) {
+    (coord >> 16, coord & (ONE - 1))
+}
+
+/// A random value in `-ONE..ONE` for a lattice point.
+#[inline]
+fn lattice_value(seed: u32, x: i32, y: i32, z: i32) -> i32 {
+    (hash(seed, x, y, z) >> 15) as i32 - ONE
+}
+
+/// The dot product of a pseudo-random 2D gradient with the offset `(dx, dy)`.
+#[inline]
+fn grad2(seed: u32, x: i32, y: i32, dx: i32, dy: i32) -> i32 {
+    match hash(seed, x, y, 0) >> 29 {
+        0 => dx + dy,
+        1 => -dx + dy,
+        2 => dx - dy,
+        3 => -dx - dy,
+        4 => dx,
+        5 => -dx,
+        6 => dy,
+        _ => -dy,
+    }
+}
+
+/// The dot product of a pseudo-random 3D gradient (one of the 12 edges of a cube) with the offset
+/// `(dx, dy, dz)`.
+#[inline]
+fn grad3(seed: u32, x: i32, y: i32, z: i32, dx: i32, dy: i32, dz: i32) -> i32 {
+    match hash(seed, x, y, z) >> 28 {
+        0 | 12 => dx + dy,
+        1 | 13 => -dx + dy,
+        2 => dx - dy,
+        3 => -dx - dy,
+        4 => dx + dz,
+        5 => -dx + dz,
+        6 => dx - dz,
+        7 => -dx - dz,
+        8 => dy + dz,
+        9 | 14 => -dy + dz,
+        10 => dy - dz,
+        _ => -dy - dz,
+    }
+}
+
+/// 2D value noise at `(x, y)`.
+pub fn value2(x: i32, y: i32, seed: u32) -> i32 {
+    let ((xi, xf), (yi, yf)) = (split(x), split(y));
+    let (u, v) = (fade(xf), fade(yf));
+    let value = |dx, dy| lattice_value(seed, xi + dx, yi + dy, 0);
+    lerp(
+        lerp(value(0, 0), value(1, 0), u),
+        lerp(value(0, 1), value(1, 1), u),
+        v,
+    )
+}
+
+/// 3D value noise at `(x, y, z)`.
+pub fn value3(x: i32, y: i32, z: i32, seed: u32) -> i32 {
+    let ((xi, xf), (yi, yf), (zi, zf)) = (split(x), split(y), split(z));
+    let (u, v, w) = (fade(xf), fade(yf), fade(zf));
+    let value = |dx, dy, dz| lattice_value(seed, xi + dx, yi + dy, zi + dz);
+    let layer = |dz| {
+        lerp(
+            lerp(value(0, 0, dz), value(1, 0, dz), u),
+            lerp(value(0, 1, dz), value(1, 1, dz), u),
+            v,
+        )
+    };
+    lerp(layer(0), layer(1), w)
+}
+
+/// 2D gradient (Perlin) noise at `(x, y)`. The result is 0 at every integer point.
+pub fn noise2(x: i32, y: i32, seed: u32) -> i32 {
+    let ((xi, xf), (yi, yf)) = (split(x), split(y));
+    let (u, v) = (fade(xf), fade(yf));
+    let grad = |dx, dy| grad2(seed, xi + dx, yi + dy, xf - dx * ONE, yf - dy * ONE);
+    let value = lerp(
+        lerp(grad(0, 0), grad(1, 0), u),
+        lerp(grad(0, 1), grad(1, 1), u),
+        v,
+    );
+    clamp(value, -ONE, ONE)
+}
+
+/// 3D gradient (Perlin) noise at `(x, y, z)`. The result is 0 at every integer point.
+pub fn noise3(x: i32, y: i32, z: i32, seed: u32) -> i32 {
+    let ((xi, xf), (yi, yf), (zi, zf)) = (split(x), split(y), split(z));
+    let (u, v, w) = (fade(xf), fade(yf), fade(zf));
+    let grad = |dx, dy, dz| {
+        grad3(
+            seed,
+            xi + dx,
+            yi + dy,
+            zi + dz,
+            xf - dx * ONE,
+            yf - dy * ONE,
+            zf - dz * ONE,
+        )
+    };
+    let layer = |dz| {
+        lerp(
+            lerp(grad(0, 0, dz), grad(1, 0, dz), u),
+            lerp(grad(0, 1, dz), grad(1, 1, dz), u),
+            v,
+        )
+    };
+    // The gradients can add up to slightly more than 1 in 3D
+    clamp(lerp(layer(0), layer(1), w), -ONE, ONE)
+}
+
+/// Sum `octaves` octaves of noise, each with twice the frequency and half the amplitude of the last,
+/// and scale the total back to `-ONE..=ONE`.
+fn fbm(octaves: u32, mut octave: impl FnMut(u32, u32) -> i32) -> i32 {
+    let octaves = octaves.clamp(1, 16);
+    let mut total = 0i64;
+    let mut amplitude = ONE as i64;
+    let mut amplitude_sum = 0;
+    for i in 0..octaves {
+        total += octave(i, 0x9e37_79b9u32.wrapping_mul(i)) as i64 * amplitude;
+        amplitude_sum += amplitude;
+        amplitude >>= 1;
+    }
+    (total / amplitude_sum) as i32
+}
+
+/// Fractal 2D gradient noise at `(x, y)`: `octaves` layers of [`noise2`] (between 1 and 16), each
+/// with twice the frequency and half the amplitude of the one before it. More octaves add finer
+/// detail, but take proportionally longer.
+pub fn fbm2(x: i32, y: i32, seed: u32, octaves: u32) -> i32 {
+    fbm(octaves, |i, offset| {
+        noise2(x.wrapping_shl(i), y.wrapping_shl(i), seed ^ offset)
+    })
+}
+
+/// Fractal 3D gradient noise at `(x, y, z)`: `octaves` layers of [`noise3`] (between 1 and 16), each
+/// with twice the frequency and half the amplitude of the one before it. More octaves add finer
+/// detail, but take proportionally longer.
+pub fn fbm3(x: i32, y: i32, z: i32, seed: u32, octaves: u32) -> i32 {
+    fbm(octaves, |i, offset| {
+        noise3(
+            x.wrapping_shl(i),
+            y.wrapping_shl(i),
+            z.wrapping_shl(i),
+            seed ^ offset,
+        )
+    })
+}
@@ -0,0 +1,166 @@
+//! Trigonometry on fixed-point numbers.
+//!
+//! Angles are measured in units of 1/65536 of a full turn, so [`FULL_TURN`] is `65536` and a right
+//! angle is `16384`. Only the bottom 16 bits of an angle are used, so angles wrap around naturally.
+//! Use [`degrees`] to convert from degrees. Results are fixed-point numbers with 16 fractional bits,
+//! so [`ONE`] (`65536`) represents 1.
+//!
+//! [`sin`] and [`cos`] use a lookup table with linear interpolation, and [`atan2`] uses CORDIC; both
+//! are accurate to within a few units in the last place.
+//!
+//! Usage:
+//! ```ignore
+//! # use mcinterface::{math::trig::{cos, sin, ONE}, turtle_pos, turtle_set, Block};
+//! // Draw a circle with a radius of 20 blocks
+//! for step in 0..128 {
+//!     let angle = step * 512;
+//!     turtle_pos(20 * cos(angle) / ONE, 64, 20 * sin(angle) / ONE);
+//!     turtle_set(Block::Gold);
+//! }
+//! ```
+
+pub use super::ONE;
+
+/// One full turn, in angle units.
+pub const FULL_TURN: i32 = 1 << 16;
+
+/// `sin` of the first quarter turn, sampled at 257 evenly spaced points.
+const SIN_TABLE: [i32; 257] = [
+    0, 402, 804, 1206, 1608, 2010, 2412, 2814, 3216, 3617,
+    4019, 4420, 4821, 5222, 5623, 6023, 6424, 6824, 7224, 7623,
+    8022, 8421, 8820, 9218, 9616, 10014, 10411, 10808, 11204, 11600,
+    11996, 12391, 12785, 13180, 13573, 13966, 14359, 14751, 15143, 15534,
+    15924, 16314, 16703, 17091, 17479, 17867, 18253, 18639, 19024, 19409,
+    19792, 20175, 20557, 20939, 21320, 21699, 22078, 22457, 22834, 23210,
+    23586, 23961, 24335, 24708, 25080, 25451, 25821, 26190, 26558, 26925,
+    27291, 27656, 28020, 28383, 28745, 29106, 29466, 29824, 30182, 30538,
+    30893, 31248, 31600, 31952, 32303, 32652, 33000, 33347, 33692, 34037,
+    34380, 34721, 35062, 35401, 35738, 36075, 36410, 36744, 37076, 37407,
+    37736, 38064, 38391, 38716, 39040, 39362, 39683, 40002, 40320, 40636,
+    40951, 41264, 41576, 41886, 42194, 42501, 42806, 43110, 43412, 43713,
+    44011, 44308, 44604, 44898, 45190, 45480, 45769, 46056, 46341, 46624,
+    46906, 47186, 47464, 47741, 48015, 48288, 48559, 48828, 49095, 49361,
+    49624, 49886, 50146, 50404, 50660, 50914, 51166, 51417, 51665, 51911,
+    52156, 52398, 52639, 52878, 53114, 53349, 53581, 53812, 54040, 54267,
+    54491, 54714, 54934, 55152, 55368, 55582, 55794, 56004, 56212, 56418,
+    56621, 56823, 57022, 57219, 57414, 57607, 57798, 57986, 58172, 58356,
+    58538, 58718, 58896, 59071, 59244, 59415, 59583, 59750, 59914, 60075,
+    60235, 60392, 60547, 60700, 60851, 60999, 61145, 61288, 61429, 61568,
+    61705, 61839, 61971, 62101, 62228, 62353, 62476, 62596, 62714, 62830,
+    62943, 63054, 63162, 63268, 63372, 63473, 63572, 63668, 63763, 63854,
+    63944, 64031, 64115, 64197, 64277, 64354, 64429, 64501, 64571, 64639,
+    64704, 64766, 64827, 64884, 64940, 64993, 65043, 65091, 65137, 65180,
+    65220, 65259, 65294, 65328, 65358, 65387, 65413, 65436, 65457, 65476,
+    65492, 65505, 65516, 65525, 65531, 65535, 65536,
+];
+
+/// `atan(2^-i)` in units of 1/2^24 of a full turn.
+const ATAN_TABLE: [i32; 16] = [
+    2097152, 1238021, 654136, 332050, 166669, 83416, 41718, 20860,
+    10430, 5215, 2608, 1304, 652, 326, 163, 81,
+];
+
+/// Convert an angle in whole degrees to angle units.
+#[inline]
+pub const fn degrees(degrees: i32) -> i32 {
+    (degrees as i64 * FULL_TURN as i64 / 360) as i32
+}
+
+/// Convert an angle in angle units to whole degrees, rounding towards zero.
+#[inline]
+pub const fn to_degrees(angle: i32) -> i32 {
+    (angle as i64 * 360 / FULL_TURN as i64) as i32
+}
+
+/// `sin` of an angle within the first quarter turn (`0..=16384`).
+#[inline]
+fn quarter_sin(angle: i32) -> i32 {
+    let (index, frac) = ((angle >> 6) as usize, angle & 63);
+    if index == SIN_TABLE.len() - 1 {
+        return SIN_TABLE[index];
+    }
+    let (a, b) = (SIN_TABLE[index], SIN_TABLE[index + 1]);
+    a + (((b - a) * frac) >> 6)
+}
+
+/// The sine of an angle, as a fixed-point number with 16 fractional bits.
+pub fn sin(angle: i32) -> i32 {
+    let angle = angle & (FULL_TURN - 1);
+    let offset = angle & (FULL_TURN / 4 - 1);
+    match angle >> 14 {
+        0 => quarter_sin(offset),
+        1 => quarter_sin(FULL_TURN / 4 - offset),
+        2 => -quarter_sin(offset),
+        _ => -quarter_sin(FULL_TURN / 4 - offset),
+    }
+}
+
+/// The cosine of an angle, as a fixed-point number with 16 fractional bits.
+#[inline]
+pub fn cos(angle: i32) -> i32 {
+    sin(angle.wrapping_add(FULL_TURN / 4))
+}
+
+/// The tangent of an angle, as a fixed-point number with 16 fractional bits. Saturates to
+/// `i32::MAX` or `i32::MIN` near a quarter turn.
+pub fn tan(angle: i32) -> i32 {
+    let (s, c) = (sin(angle) as i64, cos(angle) as i64);
+    if c == 0 {
+        return if s >= 0 { i32::MAX } else { i32::MIN };
+    }
+    ((s << 16) / c).clamp(i32::MIN as i64, i32::MAX as i64) as i32
+}
+
+/// The angle of the vector `(x, y)` from the positive x axis, in angle units from `-32768` to
+/// `32768`, like `f64::atan2(y, x)`. The components can have any scale, as long as it is the same
+/// for both. Returns 0 if both are 0.
+pub fn atan2(y: i32, x: i32) -> i32 {
+    if x == 0 && y == 0 {
+        return 0;
+    }
+    let (mut x, mut y) = (x, y);
+    // Leave room for the CORDIC gain (about 1.65)
+    while x.unsigned_abs() >= 1 << 28 || y.unsigned_abs() >= 1 << 28 {
+        x >>= 1;
+        y >>= 1;
+    }
+    // Scale small vectors up so that the shifts below keep enough precision
+    while x.unsigned_abs() < 1 << 20 && y.unsigned_abs() < 1 << 20 {
+        x <<= 1;
+        y <<= 1;
+    }
+
+    // CORDIC only converges within a quarter turn of the x axis, so rotate by a half turn first if
+    // the vector points left
+    let mut angle = 0;
+    if x < 0 {
+        angle = if y >= 0 { 1 << 23 } else { -(1 << 23) };
+        x = -x;
+        y = -y;
+    }
+    for (i, step) in ATAN_TABLE.iter().enumerate() {
+        let (dx, dy) = (y >> i, x >> i);
+        if y > 0 {
+            x += dx;
+            y -= dy;
+            angle += step;
+        } else {
+            x -= dx;
+            y += dy;
+            angle -= step;
+        }
+    }
+    (angle + (1 << 7)) >> 8
+}
+
+/// Rotate the point `(x, y)` anticlockwise around the origin by an angle, rounding to the nearest
+/// integer. Useful for rotating sprites and structures by arbitrary angles.
+pub fn rotate(x: i32, y: i32, angle: i32) -> (i32, i32) {
+    let (s, c) = (sin(angle) as i64, cos(angle) as i64);
+    let (x, y) = (x as i64, y as i64);
+    let half = 1 << 15;
+    (
+        ((x * c - y * s + half) >> 16) as i32,
+        ((x * s + y * c + half) >> 16) as i32,
+    )
+}
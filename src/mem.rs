@@ -0,0 +1,314 @@
+//! Filling, zeroing and copying memory.
+//!
+//! wasmcraft2 provides a `memset` intrinsic (re-exported as [`crate::memset`]) which fills memory
+//! much faster than a loop of stores. Despite its `*mut i32` pointer type, it works like C's
+//! `memset`: `length` is a number of bytes, and every byte is set to the lowest 8 bits of `value`.
+//! The functions in this module wrap it safely for slices and [`Pod`] values, so it can be used
+//! without `unsafe` for clearing buffers.
+//!
+//! wasmcraft2 has no equivalent intrinsic for copying memory, and every load and store becomes
+//! several commands, so [`copy_slice`] and [`move_slice`] copy a 4-byte word at a time wherever the
+//! source and destination are aligned the same way, rather than a byte at a time.
+//!
+//! Usage:
+//! ```ignore
+//! # use mcinterface::mem::{copy_slice, fill_bytes, zero};
+//! let mut buffer = [1u8; 256];
+//! fill_bytes(&mut buffer, 0xff);
+//! let mut counts = [7u32; 16];
+//! zero(&mut counts);
+//! let mut backup = [0u32; 16];
+//! copy_slice(&mut backup, &counts);
+//! ```
+
+use crate::memset;
+use crate::pod::Pod;
+use core::mem::{size_of, size_of_val, MaybeUninit};
+use core::ops::Range;
+
+/// Set `len` bytes starting at `ptr` to `value`, using the `memset` intrinsic.
+///
+/// # Safety
+/// `ptr` must be valid for writes of `len` bytes, and the bytes written must leave any values they
+/// belong to valid.
+#[inline]
+pub unsafe fn fill_raw(ptr: *mut u8, value: u8, len: usize) {
+    if len != 0 {
+        memset(ptr as *mut i32, value as i32, len as u32);
+    }
+}
+
+/// Set every byte of a slice to `value`.
+#[inline]
+pub fn fill_bytes(buf: &mut [u8], value: u8) {
+    // SAFETY: the slice is valid for writes of its length, and any byte is a valid u8
+    unsafe { fill_raw(buf.as_mut_ptr(), value, buf.len()) }
+}
+
+/// Set every byte of a value to zero.
+#[inline]
+pub fn zero<T: Pod>(value: &mut T) {
+    fill_bytes(bytes_of_mut(value), 0);
+}
+
+/// Set every byte of a slice of values to zero.
+#[inline]
+pub fn zero_slice<T: Pod>(values: &mut [T]) {
+    // SAFETY: the slice is valid for writes of its size, and `T: Pod` means all zeroes is a valid
+    // value
+    unsafe { fill_raw(values.as_mut_ptr() as *mut u8, 0, size_of_val(values)) }
+}
+
+/// Create a value with every byte set to zero.
+#[inline]
+pub fn zeroed<T: Pod>() -> T {
+    let mut value = MaybeUninit::<T>::uninit();
+    // SAFETY: the value is valid for writes of its size, and `T: Pod` means all zeroes is a valid
+    // value
+    unsafe {
+        fill_raw(value.as_mut_ptr() as *mut u8, 0, size_of::<T>());
+        value.assume_init()
+    }
+}
+
+/// View the bytes of a value.
+#[inline]
+pub fn bytes_of<T: Pod>(value: &T) -> &[u8] {
+    // SAFETY: `T: Pod` means the value has no padding, so all of its bytes are initialised
+    unsafe { core::slice::from_raw_parts(value as *const T as *const u8, size_of::<T>()) }
+}
+
+/// View the bytes of a value mutably.
+#[inline]
+pub fn bytes_of_mut<T: Pod>(value: &mut T) -> &mut [u8] {
+    // SAFETY: `T: Pod` means the value has no padding and any bytes written make a valid value
+    unsafe { core::slice::from_raw_parts_mut(value as *mut T as *mut u8, size_of::<T>()) }
+}
+
+/// Bytes and words are copied as `MaybeUninit` so that padding bytes can be copied too.
+type Byte = MaybeUninit<u8>;
+type Word = MaybeUninit<u32>;
+
+/// Copy `len` bytes from `src` to `dst`, a word at a time where possible.
+///
+/// # Safety
+/// `src` must be valid for reads and `dst` valid for writes of `len` bytes, the regions must not
+/// overlap, and the bytes written must leave any values they belong to valid.
+pub unsafe fn copy_raw(dst: *mut u8, src: *const u8, len: usize) {
+    let mut i = 0;
+    if (dst as usize) & 3 == (src as usize) & 3 {
+        // Copy single bytes up to the first word boundary, then whole words
+        while i < len && (dst as usize + i) & 3 != 0 {
+            *(dst.add(i) as *mut Byte) = *(src.add(i) as *const Byte);
+            i += 1;
+        }
+        while i + 4 <= len {
+            *(dst.add(i) as *mut Word) = *(src.add(i) as *const Word);
+            i += 4;
+        }
+    }
+    while i < len {
+        *(dst.add(i) as *mut Byte) = *(src.add(i) as *const Byte);
+        i += 1;
+    }
+}
+
+/// Copy `len` bytes from `src` to `dst`, a word at a time where possible. Unlike [`copy_raw`], the
+/// regions may overlap.
+///
+/// # Safety
+/// `src` must be valid for reads and `dst` valid for writes of `len` bytes, and the bytes written
+/// must leave any values they belong to valid.
+pub unsafe fn move_raw(dst: *mut u8, src: *const u8, len: usize) {
+    if (dst as usize) <= (src as usize) || (dst as usize) >= (src as usize) + len {
+        // Copying forwards never overwrites a byte before it has been read
+        copy_raw(dst, src, len);
+        return;
+    }
+    // The destination starts inside the source, so copy backwards
+    let mut i = len;
+    if (dst as usize) & 3 == (src as usize) & 3 {
+        while i > 0 && (dst as usize + i) & 3 != 0 {
+            i -= 1;
+            *(dst.add(i) as *mut Byte) = *(src.add(i) as *const Byte);
+        }
+        while i >= 4 {
+            i -= 4;
+            *(dst.add(i) as *mut Word) = *(src.add(i) as *const Word);
+        }
+    }
+    while i > 0 {
+        i -= 1;
+        *(dst.add(i) as *mut Byte) = *(src.add(i) as *const Byte);
+    }
+}
+
+/// Copy every element of `src` into `dst`, like [`slice::copy_from_slice`] but a word at a time.
+///
+/// # Panics
+/// Panics if the slices have different lengths.
+pub fn copy_slice<T: Copy>(dst: &mut [T], src: &[T]) {
+    assert_eq!(dst.len(), src.len(), "slices have different lengths");
+    // SAFETY: the slices are valid for their size, and cannot overlap since `dst` is a unique
+    // reference. Copying the bytes of a `Copy` type makes a valid copy of it.
+    unsafe { copy_raw(dst.as_mut_ptr() as *mut u8, src.as_ptr() as *const u8, size_of_val(src)) }
+}
+
+/// Copy the elements in the range `src` of a slice to the position `dest` in the same slice, like
+/// [`slice::copy_within`] but a word at a time. The ranges may overlap.
+///
+/// # Panics
+/// Panics if either range is out of bounds, or if the end of `src` is before its start.
+pub fn move_slice<T: Copy>(slice: &mut [T], src: Range<usize>, dest: usize) {
+    assert!(src.start <= src.end, "range start is after its end");
+    assert!(src.end <= slice.len(), "source range out of bounds");
+    let count = src.end - src.start;
+    assert!(dest <= slice.len() - count, "destination out of bounds");
+    let ptr = slice.as_mut_ptr();
+    // SAFETY: both ranges were checked to be inside the slice, and `move_raw` handles overlap.
+    // Copying the bytes of a `Copy` type makes a valid copy of it.
+    unsafe {
+        move_raw(
+            ptr.add(dest) as *mut u8,
+            ptr.add(src.start) as *const u8,
+            count * size_of::<T>(),
+        )
+    }
+}
+
+#[cfg(all(test, feature = "simulator"))]
+mod tests {
+    use super::*;
+
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    #[repr(C)]
+    struct State {
+        position: (i32, i32, i32),
+        counts: [u16; 4],
+        flags: u32,
+    }
+
+    // SAFETY: `State` is `repr(C)`, contains only `Pod` fields and has no padding
+    unsafe impl Pod for State {}
+
+    const STATE: State = State { position: (1, -2, 3), counts: [4, 5, 6, 7], flags: 0xdead_beef };
+
+    #[test]
+    fn memset_uses_low_byte() {
+        let mut buf = [0u8; 16];
+        unsafe { memset(buf.as_mut_ptr() as *mut i32, 0x1234_56ab, 16) };
+        assert_eq!(buf, [0xab; 16]);
+        unsafe { memset(buf.as_mut_ptr() as *mut i32, -1, 16) };
+        assert_eq!(buf, [0xff; 16]);
+        // The length is in bytes, not words
+        unsafe { memset(buf.as_mut_ptr().add(3) as *mut i32, 0x100, 5) };
+        assert_eq!(buf[..3], [0xff; 3]);
+        assert_eq!(buf[3..8], [0; 5]);
+        assert_eq!(buf[8..], [0xff; 8]);
+    }
+
+    #[test]
+    fn fill_zero_length() {
+        let mut buf = [7u8; 8];
+        unsafe { fill_raw(buf.as_mut_ptr(), 0, 0) };
+        fill_bytes(&mut buf[4..4], 0);
+        zero_slice::<u32>(&mut []);
+        assert_eq!(buf, [7; 8]);
+    }
+
+    #[test]
+    fn fill_bytes_range() {
+        let mut buf = [1u8; 10];
+        fill_bytes(&mut buf[1..9], 0xee);
+        assert_eq!(buf, [1, 0xee, 0xee, 0xee, 0xee, 0xee, 0xee, 0xee, 0xee, 1]);
+    }
+
+    #[test]
+    fn zero_pod_values() {
+        let mut state = STATE;
+        zero(&mut state);
+        assert_eq!(state, State { position: (0, 0, 0), counts: [0; 4], flags: 0 });
+        assert_eq!(zeroed::<State>(), state);
+        assert_eq!(zeroed::<[u64; 3]>(), [0; 3]);
+        assert_eq!(zeroed::<i32>(), 0);
+
+        let mut states = [STATE; 3];
+        zero_slice(&mut states[1..]);
+        assert_eq!(states, [STATE, state, state]);
+        assert!(bytes_of(&states[1]).iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn copy_slice_values() {
+        let src = [STATE, zeroed(), STATE];
+        let mut dst = [zeroed::<State>(); 3];
+        copy_slice(&mut dst, &src);
+        assert_eq!(dst, src);
+    }
+
+    #[test]
+    #[should_panic(expected = "different lengths")]
+    fn copy_slice_length_mismatch() {
+        copy_slice(&mut [0u8; 3], &[0u8; 4]);
+    }
+
+    /// A buffer of distinct bytes.
+    fn pattern<const N: usize>() -> [u8; N] {
+        core::array::from_fn(|i| i as u8)
+    }
+
+    #[test]
+    fn copy_misaligned() {
+        // Every combination of source and destination alignment, including ones that can never be
+        // copied a word at a time
+        for src_start in 0..4 {
+            for dst_start in 0..4 {
+                for len in 0..24 {
+                    let src = pattern::<32>();
+                    let mut dst = [0xffu8; 32];
+                    let (src, dst_range) = (&src[src_start..src_start + len], dst_start..dst_start + len);
+                    let mut expected = dst;
+                    expected[dst_range.clone()].copy_from_slice(src);
+                    copy_slice(&mut dst[dst_range], src);
+                    assert_eq!(dst, expected, "src {src_start}, dst {dst_start}, len {len}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn move_overlapping() {
+        // Destinations both before and after the source, overlapping it and not, with every
+        // alignment
+        for src_start in 0..8 {
+            for dest in 0..16 {
+                for len in 0..=(32 - src_start.max(dest)).min(20) {
+                    let mut buf = pattern::<32>();
+                    let mut expected = buf;
+                    expected.copy_within(src_start..src_start + len, dest);
+                    move_slice(&mut buf, src_start..src_start + len, dest);
+                    assert_eq!(buf, expected, "src {src_start}, dest {dest}, len {len}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn move_overlapping_words() {
+        let mut values: [u16; 12] = core::array::from_fn(|i| i as u16 * 0x101);
+        let mut expected = values;
+        expected.copy_within(1..9, 3);
+        move_slice(&mut values, 1..9, 3);
+        assert_eq!(values, expected);
+
+        expected.copy_within(4..11, 1);
+        move_slice(&mut values, 4..11, 1);
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "destination out of bounds")]
+    fn move_slice_out_of_bounds() {
+        move_slice(&mut [0u8; 8], 0..4, 5);
+    }
+}
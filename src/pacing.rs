@@ -0,0 +1,153 @@
+//! Spreading long loops over several game ticks.
+//!
+//! wasmcraft2 inserts [`mc_sleep`] calls into loops automatically, but a loop whose body runs a lot of
+//! commands can still hit Minecraft's per-tick command limit. Rather than sprinkling [`mc_sleep`]
+//! calls through the loop body, wrap the iterator with [`Ticked::ticked`] (or use
+//! [`for_ticked!`](crate::for_ticked)) to sleep every `n` iterations.
+//!
+//! When the amount of work per iteration varies, [`yield_if_needed`] can be used instead: it keeps an
+//! approximate count of the commands run so far this tick, and sleeps before running more work if it
+//! would go over the budget set with [`budget_hint`].
+
+use crate::global::global;
+use crate::{mc_sleep, ticks};
+
+/// The default budget for [`yield_if_needed`]: half of Minecraft's default `maxCommandChainLength`,
+/// which leaves room for the commands wasmcraft2 runs outside of your code.
+pub const DEFAULT_BUDGET: u32 = 32768;
+
+global! {
+    /// The number of commands per tick allowed by [`yield_if_needed`].
+    static BUDGET: u32 = DEFAULT_BUDGET;
+}
+
+global! {
+    /// The tick in which work was last counted by [`yield_if_needed`], and the work counted in it.
+    static SPENT: (u32, u32) = (0, 0);
+}
+
+/// Set the approximate number of commands that [`yield_if_needed`] allows per tick, which is
+/// [`DEFAULT_BUDGET`] by default. If your world's `maxCommandChainLength` gamerule has been changed,
+/// or your program is still stalling the server, set this accordingly.
+#[inline]
+pub fn budget_hint(commands_per_tick: u32) {
+    BUDGET.set(commands_per_tick.max(1))
+}
+
+/// The approximate number of commands that can still be run this tick before [`yield_if_needed`]
+/// sleeps.
+pub fn remaining_budget() -> u32 {
+    let (tick, spent) = SPENT.get();
+    let spent = if tick == ticks() { spent } else { 0 };
+    BUDGET.get().saturating_sub(spent)
+}
+
+/// Account for work that is about to be done, costing approximately `cost` commands. If this would
+/// go over the budget for the current tick (see [`budget_hint`]), [`mc_sleep`] is called first and
+/// the work is counted towards the next tick instead. Returns whether it slept.
+///
+/// The count is reset whenever a tick passes through [`mc_sleep`], so work done in other places that
+/// sleep does not need to be accounted for. Sleeps inserted automatically by wasmcraft2 are not
+/// noticed, so the count is an overestimate.
+///
+/// Costs only need to be rough: a single wasm instruction typically becomes a handful of commands,
+/// and each block placed or read by the turtle costs a few more. Measuring a loop with
+/// [`profile_scope!`](crate::profile_scope) while adjusting the costs is a good way to find suitable
+/// values.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{pacing::yield_if_needed, region::Region};
+/// for pos in region.iter() {
+///     yield_if_needed(50);
+///     // ...
+/// }
+/// ```
+pub fn yield_if_needed(cost: u32) -> bool {
+    let now = ticks();
+    let (tick, spent) = SPENT.get();
+    let spent = if tick == now { spent } else { 0 };
+    match spent.checked_add(cost) {
+        Some(total) if total <= BUDGET.get() => {
+            SPENT.set((now, total));
+            false
+        }
+        _ => {
+            mc_sleep();
+            SPENT.set((ticks(), cost));
+            true
+        }
+    }
+}
+
+/// An iterator that calls [`mc_sleep`] every `n` items, created by [`Ticked::ticked`].
+pub struct TickedIter<I> {
+    iter: I,
+    n: u32,
+    count: u32,
+}
+
+impl<I: Iterator> Iterator for TickedIter<I> {
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        let item = self.iter.next()?;
+        if self.count >= self.n {
+            mc_sleep();
+            self.count = 0;
+        }
+        self.count += 1;
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Adds [`ticked`](Ticked::ticked) to every iterator.
+pub trait Ticked: Iterator + Sized {
+    /// Call [`mc_sleep`] after every `n` items (an `n` of 0 is treated as 1). The sleep happens
+    /// before the next item is taken, so there is no sleep after the last item.
+    ///
+    /// Usage:
+    /// ```ignore
+    /// # use mcinterface::{pacing::Ticked, turtle_pos, turtle_set, Block};
+    /// for x in (0..1000).ticked(50) {
+    ///     turtle_pos(x, 64, 0);
+    ///     turtle_set(Block::Cobblestone);
+    /// }
+    /// ```
+    #[inline]
+    fn ticked(self, n: u32) -> TickedIter<Self> {
+        TickedIter {
+            iter: self,
+            n: n.max(1),
+            count: 0,
+        }
+    }
+}
+
+impl<I: Iterator> Ticked for I {}
+
+/// A `for` loop that calls [`mc_sleep`](crate::mc_sleep) every `n` iterations, equivalent to
+/// iterating over [`Ticked::ticked`](crate::pacing::Ticked::ticked). `break` and `continue` work as
+/// in a normal `for` loop.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{for_ticked, turtle_pos, turtle_set, Block};
+/// for_ticked!(50, x in 0..1000, {
+///     turtle_pos(x, 64, 0);
+///     turtle_set(Block::Cobblestone);
+/// });
+/// ```
+#[macro_export]
+macro_rules! for_ticked {
+    ($n:expr, $pat:pat in $iter:expr, $body:block) => {
+        for $pat in $crate::pacing::Ticked::ticked(::core::iter::IntoIterator::into_iter($iter), $n)
+            $body
+    };
+}
@@ -0,0 +1,137 @@
+//! The panic handler.
+//!
+//! By default, a panic prints a generic message to the game chat and then loops forever. With the
+//! `panic-message` feature enabled, the panic message and the location of the panic are printed too;
+//! this is disabled by default because formatting the message pulls in `core::fmt` machinery, which
+//! makes the datapack larger even for programs that never panic.
+//!
+//! Chat messages are easy to miss in a long-running program, so [`set_panic_beacon`] can be used to
+//! make the panic handler also build a column of redstone blocks somewhere visible, and
+//! [`set_panic_hook`] can be used to run custom code (e.g. saving a
+//! [`Checkpoint`](crate::checkpoint::Checkpoint) or drawing an error screen) when a panic occurs.
+//!
+//! While tests are being run by the [`testing`](crate::testing) framework, a panic fails the current
+//! test and the remaining tests are run instead.
+
+use crate::global::global;
+
+global! {
+    /// The bottom of the panic beacon and its height, if one has been set.
+    static PANIC_BEACON: Option<((i32, i32, i32), u32)> = None;
+}
+
+/// Make the panic handler place a column of `height` [`Block::Redstone`](crate::Block::Redstone) blocks with its bottom at
+/// the given position, so that a crashed program is obvious from anywhere the position is visible.
+///
+/// The beacon is placed after the panic has been reported in chat. Calling this again moves the
+/// beacon; [`clear_panic_beacon`] disables it.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::panic::set_panic_beacon;
+/// set_panic_beacon(0, 64, 0, 16);
+/// ```
+#[inline]
+pub fn set_panic_beacon(x: i32, y: i32, z: i32, height: u32) {
+    PANIC_BEACON.set(Some(((x, y, z), height)))
+}
+
+/// Stop the panic handler from placing a beacon.
+#[inline]
+pub fn clear_panic_beacon() {
+    PANIC_BEACON.set(None)
+}
+
+/// Get the position and height of the panic beacon set with [`set_panic_beacon`], if any.
+#[inline]
+pub fn panic_beacon() -> Option<((i32, i32, i32), u32)> {
+    PANIC_BEACON.get()
+}
+
+global! {
+    /// The function set with [`set_panic_hook`], if any.
+    static PANIC_HOOK: Option<fn(&core::panic::PanicInfo)> = None;
+}
+
+global! {
+    /// Whether the panic handler is already running, so that a panic inside the hook does not run the
+    /// hook again.
+    #[cfg(not(any(test, feature = "simulator")))]
+    static PANICKING: bool = false;
+}
+
+/// Set a function to be called by the panic handler, after the panic has been reported in chat and
+/// before the panic beacon (if any) is placed. Replaces any previously set hook.
+///
+/// If the hook itself panics, it is not called again; the second panic is reported and the handler
+/// continues as if there were no hook.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{panic::set_panic_hook, println};
+/// set_panic_hook(|_info| {
+///     println("saving progress...");
+///     // ...
+/// });
+/// ```
+#[inline]
+pub fn set_panic_hook(hook: fn(&core::panic::PanicInfo)) {
+    PANIC_HOOK.set(Some(hook))
+}
+
+/// Remove the hook set with [`set_panic_hook`], returning it.
+#[inline]
+pub fn take_panic_hook() -> Option<fn(&core::panic::PanicInfo)> {
+    PANIC_HOOK.replace(None)
+}
+
+/// Place the panic beacon, if one has been set.
+#[cfg(not(any(test, feature = "simulator")))]
+fn place_beacon() {
+    if let Some(((x, y, z), height)) = panic_beacon() {
+        for dy in 0..height as i32 {
+            crate::turtle_pos(x, y + dy, z);
+            crate::turtle_set(crate::Block::Redstone);
+        }
+    }
+}
+
+#[cfg(not(any(test, feature = "simulator")))]
+#[panic_handler]
+fn panic(info: &core::panic::PanicInfo) -> ! {
+    #[cfg(feature = "testing")]
+    crate::testing::on_panic(info);
+    report(info);
+    let hook = if PANICKING.replace(true) { None } else { PANIC_HOOK.get() };
+    if let Some(hook) = hook {
+        hook(info);
+    }
+    place_beacon();
+    loop {
+        crate::mc_sleep();
+    }
+}
+
+#[cfg(not(any(test, feature = "simulator")))]
+fn report(info: &core::panic::PanicInfo) {
+    crate::println("RUST PANIC - entering infinite loop!");
+    report_message(info);
+}
+
+/// Print the panic message and location, if the `panic-message` feature is enabled.
+#[cfg(all(not(any(test, feature = "simulator")), feature = "panic-message"))]
+pub(crate) fn report_message(info: &core::panic::PanicInfo) {
+    match info.location() {
+        Some(location) => crate::println!(
+            "panicked at {}:{}: {}",
+            location.file(),
+            location.line(),
+            info.message()
+        ),
+        None => crate::println!("panicked: {}", info.message()),
+    }
+}
+
+/// Print the panic message and location, if the `panic-message` feature is enabled.
+#[cfg(all(not(any(test, feature = "simulator")), not(feature = "panic-message")))]
+pub(crate) fn report_message(_info: &core::panic::PanicInfo) {}
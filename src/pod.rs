@@ -0,0 +1,43 @@
+//! Plain old data types.
+//!
+//! Contains the [`Pod`] trait, implemented by types that can be safely converted to and from raw bytes.
+
+/// A type for which every bit pattern is a valid value, and which contains no padding bytes or
+/// pointers.
+///
+/// Types implementing this trait can be safely converted to and from raw bytes, which allows them to
+/// be stored in the world (see [`Checkpoint`](crate::checkpoint::Checkpoint)) or cleared by filling
+/// their memory with zeroes.
+///
+/// # Safety
+///
+/// Implementors must ensure that:
+/// - every bit pattern of `size_of::<Self>()` bytes is a valid value of the type,
+/// - the type contains no padding bytes (for structs, use `#[repr(C)]` and make sure every field is
+///   aligned without padding),
+/// - the type contains no references or pointers, since they would be meaningless after being
+///   restored.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::pod::Pod;
+/// #[repr(C)]
+/// #[derive(Copy, Clone)]
+/// struct State {
+///     generation: u32,
+///     cells: [u8; 64],
+/// }
+///
+/// unsafe impl Pod for State {}
+/// ```
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($t:ty),*) => {
+        $(unsafe impl Pod for $t {})*
+    };
+}
+
+impl_pod!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
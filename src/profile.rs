@@ -0,0 +1,65 @@
+//! Measuring how many game ticks parts of a program take.
+//!
+//! [`profile_scope!`](crate::profile_scope) prints the number of ticks spent in the rest of the
+//! current scope when the scope exits:
+//! ```ignore
+//! # use mcinterface::profile_scope;
+//! fn generate_terrain() {
+//!     profile_scope!("terrain");
+//!     // ...
+//! } // prints "[profile] terrain: 37 ticks"
+//! ```
+//!
+//! Ticks are counted with [`ticks()`](crate::ticks), which only counts calls to
+//! [`mc_sleep`](crate::mc_sleep) made through this crate, so sleeps inserted automatically by
+//! wasmcraft2 are not included. For an accurate measurement, call [`mc_sleep`](crate::mc_sleep)
+//! explicitly in long loops (e.g. with [`pacing`](crate::pacing)).
+
+use crate::ticks;
+
+/// Prints the number of ticks since it was created when it is dropped. Usually created with
+/// [`profile_scope!`](crate::profile_scope).
+#[must_use = "the elapsed time is printed when the scope is dropped"]
+pub struct ProfileScope {
+    name: &'static str,
+    start: u32,
+}
+
+impl ProfileScope {
+    /// Start timing a scope with the given name.
+    #[inline]
+    pub fn new(name: &'static str) -> Self {
+        Self { name, start: ticks() }
+    }
+
+    /// The number of ticks since this scope was created.
+    #[inline]
+    pub fn elapsed(&self) -> u32 {
+        ticks().wrapping_sub(self.start)
+    }
+}
+
+impl Drop for ProfileScope {
+    fn drop(&mut self) {
+        crate::print_str("[profile] ");
+        crate::print_str(self.name);
+        crate::print_str(": ");
+        crate::fmt::print_u32(self.elapsed());
+        crate::println(" ticks");
+    }
+}
+
+/// Print the number of game ticks spent in the rest of the current scope when it exits, prefixed by
+/// the given name. See the [`profile`](crate::profile) module.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::profile_scope;
+/// profile_scope!("pathfinding");
+/// ```
+#[macro_export]
+macro_rules! profile_scope {
+    ($name:expr) => {
+        let _profile_scope = $crate::profile::ProfileScope::new($name);
+    };
+}
@@ -0,0 +1,213 @@
+//! Pseudo-random number generation.
+//!
+//! Contains two small generators, [`Xorshift32`] (fastest, using only 32-bit operations) and
+//! [`SplitMix64`] (better quality, and any seed is good), and the [`Rng`] trait, which adds helpers
+//! such as [`Rng::gen_range`] and [`Rng::choose`] to both of them.
+//!
+//! A program started the same way always does the same thing, so something from the world is needed
+//! to get different results each time. [`seed_from_blocks`] and [`seed_from_region`] mix the blocks
+//! at the given positions into a seed, so the same build can be varied by e.g. placing a few random
+//! blocks by hand, or by pointing them at terrain that players change.
+//!
+//! Usage:
+//! ```ignore
+//! # use mcinterface::{rand::{seed_from_blocks, Rng, SplitMix64}, turtle_pos, turtle_set, Block};
+//! let mut rng = SplitMix64::new(seed_from_blocks(&[(0, 64, 0), (1, 64, 0), (2, 64, 0)]));
+//! for x in 0..100 {
+//!     let height = rng.gen_range(1, 8);
+//!     for y in 0..height {
+//!         turtle_pos(x, 64 + y, 10);
+//!         turtle_set(*rng.choose(&[Block::Cobblestone, Block::Andesite]).unwrap());
+//!     }
+//! }
+//! ```
+
+use crate::region::Region;
+use crate::{turtle_get, turtle_pos};
+
+/// A source of random numbers.
+///
+/// Only [`next_u32`](Rng::next_u32) needs to be implemented; the other methods are built on it.
+pub trait Rng {
+    /// Generate a random `u32`.
+    fn next_u32(&mut self) -> u32;
+
+    /// Generate a random `u64`.
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        (self.next_u32() as u64) << 32 | self.next_u32() as u64
+    }
+
+    /// Generate a random number below `bound`, with every value equally likely.
+    ///
+    /// This avoids division except in rare cases, since division is slow in wasmcraft2.
+    ///
+    /// # Panics
+    /// Panics if `bound` is 0.
+    fn gen_below(&mut self, bound: u32) -> u32 {
+        assert!(bound != 0, "cannot generate a number below 0");
+        // Lemire's method: the high half of a 64-bit product is uniform once the few low halves that
+        // would bias it are rejected
+        let mut product = self.next_u32() as u64 * bound as u64;
+        if (product as u32) < bound {
+            let threshold = bound.wrapping_neg() % bound;
+            while (product as u32) < threshold {
+                product = self.next_u32() as u64 * bound as u64;
+            }
+        }
+        (product >> 32) as u32
+    }
+
+    /// Generate a random number in the range `lo..hi`, with every value equally likely.
+    ///
+    /// # Panics
+    /// Panics if `lo >= hi`.
+    #[inline]
+    fn gen_range(&mut self, lo: i32, hi: i32) -> i32 {
+        assert!(lo < hi, "empty range");
+        let width = hi.wrapping_sub(lo) as u32;
+        lo.wrapping_add(self.gen_below(width) as i32)
+    }
+
+    /// Generate a random number in the range `lo..=hi`, with every value equally likely.
+    ///
+    /// # Panics
+    /// Panics if `lo > hi`.
+    fn gen_range_inclusive(&mut self, lo: i32, hi: i32) -> i32 {
+        assert!(lo <= hi, "empty range");
+        let width = hi.wrapping_sub(lo) as u32;
+        match width.checked_add(1) {
+            Some(bound) => lo.wrapping_add(self.gen_below(bound) as i32),
+            None => self.next_u32() as i32,
+        }
+    }
+
+    /// Return `true` with a probability of `numerator / denominator`.
+    ///
+    /// # Panics
+    /// Panics if `denominator` is 0.
+    #[inline]
+    fn gen_ratio(&mut self, numerator: u32, denominator: u32) -> bool {
+        self.gen_below(denominator) < numerator
+    }
+
+    /// Return `true` or `false` with equal probability.
+    #[inline]
+    fn gen_bool(&mut self) -> bool {
+        self.next_u32() >> 31 != 0
+    }
+
+    /// Choose a random element of a slice, or `None` if it is empty.
+    #[inline]
+    fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            None
+        } else {
+            items.get(self.gen_below(items.len() as u32) as usize)
+        }
+    }
+
+    /// Shuffle a slice into a random order.
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.gen_below(i as u32 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+/// The xorshift32 generator: 32 bits of state, and only shifts and XORs to generate numbers.
+///
+/// This is the cheapest generator to run in wasmcraft2, and is good enough for most procedural
+/// building, but its output has some detectable patterns (e.g. the lowest bits are weak).
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Xorshift32 {
+    state: u32,
+}
+
+impl Xorshift32 {
+    /// Create a generator from a seed. xorshift32 cannot have a state of 0, so a seed of 0 is
+    /// replaced by a fixed non-zero seed.
+    #[inline]
+    pub const fn new(seed: u32) -> Self {
+        Self {
+            state: if seed == 0 { 0x9e37_79b9 } else { seed },
+        }
+    }
+}
+
+impl Rng for Xorshift32 {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+}
+
+/// The SplitMix64 generator: 64 bits of state, and high quality output for any seed.
+///
+/// This is slower than [`Xorshift32`] in wasmcraft2, since it uses 64-bit multiplication.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Create a generator from a seed.
+    #[inline]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+}
+
+impl Rng for SplitMix64 {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Mix a value into a seed.
+#[inline]
+fn mix(seed: u64, value: i32) -> u64 {
+    SplitMix64::new(seed ^ value as u32 as u64).next_u64()
+}
+
+/// Make a seed from the blocks at the given positions, using [`turtle_get`]. The result depends on
+/// which block is at each position, so changing any of them gives an unrelated seed. The turtle is
+/// left at the last position.
+pub fn seed_from_blocks(positions: &[(i32, i32, i32)]) -> u64 {
+    let mut seed = 0;
+    for &(x, y, z) in positions {
+        turtle_pos(x, y, z);
+        seed = mix(seed, turtle_get() as i32);
+    }
+    seed
+}
+
+/// Make a seed from every block in a region, using [`turtle_get`]. The result depends on which
+/// block is at each position, so changing any of them gives an unrelated seed. The turtle is left at
+/// the last position in the region.
+///
+/// Every block is read, so large regions take a long time; [`seed_from_blocks`] can be used to sample
+/// a few positions instead.
+pub fn seed_from_region(region: &Region) -> u64 {
+    let mut seed = 0;
+    for (x, y, z) in region.iter() {
+        turtle_pos(x, y, z);
+        seed = mix(seed, turtle_get() as i32);
+    }
+    seed
+}
@@ -0,0 +1,222 @@
+//! Cuboid regions of the world.
+//!
+//! Contains [`Region`], which describes a cuboid by its minimum corner and span. Like the wasmcraft2
+//! functions that take spans, the span of a region is its size minus one along each axis, so a
+//! region with a span of `(0, 0, 0)` contains a single block. To avoid off-by-one errors, regions can
+//! also be created from their size with [`Region::from_size`] or from two corners with
+//! [`Region::from_corners`].
+//!
+//! Positions are `(x, y, z)` tuples, but functions that take a position also accept an
+//! [`IVec3`].
+
+use crate::vec::IVec3;
+use crate::{turtle_copy_region, turtle_fill, turtle_paste_region_masked, turtle_pos, Block};
+
+/// A cuboid region of the world, described by its minimum corner and its span along each axis.
+///
+/// All components of the span must be non-negative.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{Block, region::Region};
+/// let floor = Region::from_size((0, 64, 0), (16, 1, 16));
+/// floor.fill(Block::Cobblestone);
+/// for (x, y, z) in floor.expand(1).unwrap().iter() {
+///     // ...
+/// }
+/// ```
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub struct Region {
+    /// The minimum corner of the region.
+    pub origin: (i32, i32, i32),
+    /// The size of the region minus one along each axis.
+    pub span: (i32, i32, i32),
+}
+
+impl Region {
+    /// Create a region from its minimum corner and span.
+    #[inline(always)]
+    pub const fn new(origin: (i32, i32, i32), span: (i32, i32, i32)) -> Self {
+        Self { origin, span }
+    }
+
+    /// Create a region from its minimum corner and size. All components of the size must be at
+    /// least 1.
+    #[inline(always)]
+    pub const fn from_size(origin: (i32, i32, i32), size: (i32, i32, i32)) -> Self {
+        Self { origin, span: (size.0 - 1, size.1 - 1, size.2 - 1) }
+    }
+
+    /// Create the smallest region containing both of the given positions.
+    pub fn from_corners(a: impl Into<IVec3>, b: impl Into<IVec3>) -> Self {
+        let (a, b) = (a.into(), b.into());
+        let (min, max) = (a.min(b), a.max(b));
+        Self { origin: min.to_tuple(), span: (max - min).to_tuple() }
+    }
+
+    /// The minimum corner of the region.
+    #[inline(always)]
+    pub const fn min(&self) -> (i32, i32, i32) {
+        self.origin
+    }
+
+    /// The maximum corner of the region (inclusive).
+    #[inline(always)]
+    pub const fn max(&self) -> (i32, i32, i32) {
+        (self.origin.0 + self.span.0, self.origin.1 + self.span.1, self.origin.2 + self.span.2)
+    }
+
+    /// The size of the region along each axis.
+    #[inline(always)]
+    pub const fn size(&self) -> (i32, i32, i32) {
+        (self.span.0 + 1, self.span.1 + 1, self.span.2 + 1)
+    }
+
+    /// The number of blocks in the region.
+    #[inline(always)]
+    pub const fn volume(&self) -> usize {
+        let (x, y, z) = self.size();
+        x as usize * y as usize * z as usize
+    }
+
+    /// The minimum and maximum (inclusive) corners of the region.
+    #[inline]
+    pub fn corners(&self) -> (IVec3, IVec3) {
+        (self.min().into(), self.max().into())
+    }
+
+    /// Check whether a position is inside the region.
+    pub fn contains(&self, pos: impl Into<IVec3>) -> bool {
+        let IVec3 { x, y, z } = pos.into();
+        let (max_x, max_y, max_z) = self.max();
+        (self.origin.0..=max_x).contains(&x)
+            && (self.origin.1..=max_y).contains(&y)
+            && (self.origin.2..=max_z).contains(&z)
+    }
+
+    /// Get the region contained in both this region and another, or `None` if they do not overlap.
+    pub fn intersect(&self, other: &Region) -> Option<Region> {
+        let (a, b) = (self.max(), other.max());
+        let min = (
+            self.origin.0.max(other.origin.0),
+            self.origin.1.max(other.origin.1),
+            self.origin.2.max(other.origin.2),
+        );
+        let max = (a.0.min(b.0), a.1.min(b.1), a.2.min(b.2));
+        if min.0 <= max.0 && min.1 <= max.1 && min.2 <= max.2 {
+            Some(Region::from_corners(min, max))
+        } else {
+            None
+        }
+    }
+
+    /// Grow the region by `amount` blocks in every direction. A negative amount shrinks the region;
+    /// if it would shrink to nothing along any axis, `None` is returned.
+    pub fn expand(&self, amount: i32) -> Option<Region> {
+        let span = (self.span.0 + 2 * amount, self.span.1 + 2 * amount, self.span.2 + 2 * amount);
+        if span.0 < 0 || span.1 < 0 || span.2 < 0 {
+            return None;
+        }
+        let origin = (self.origin.0 - amount, self.origin.1 - amount, self.origin.2 - amount);
+        Some(Region { origin, span })
+    }
+
+    /// Move the region by an offset.
+    pub fn translate(&self, offset: impl Into<IVec3>) -> Region {
+        let origin = IVec3::from(self.origin) + offset.into();
+        Region { origin: origin.to_tuple(), span: self.span }
+    }
+
+    /// Iterate over the positions in the region.
+    ///
+    /// Positions are yielded with x changing fastest, then z, then y (i.e. layer by layer from the
+    /// bottom up), which minimises the number of turtle coordinates that change between consecutive
+    /// positions.
+    #[inline]
+    pub fn iter(&self) -> RegionIter {
+        RegionIter { region: *self, next: Some(self.origin) }
+    }
+
+    /// Fill the region with a block using [`turtle_fill()`].
+    ///
+    /// This moves the turtle to the origin of the region.
+    ///
+    /// This function is unstable, and may cause wasmcraft2 to fail compilation.
+    #[inline(always)]
+    pub fn fill(&self, block: Block) {
+        turtle_pos(self.origin.0, self.origin.1, self.origin.2);
+        turtle_fill(block, self.span.0, self.span.1, self.span.2);
+    }
+
+    /// Copy the region using [`turtle_copy_region()`].
+    ///
+    /// This moves the turtle to the origin of the region.
+    #[inline(always)]
+    pub fn copy(&self) {
+        turtle_pos(self.origin.0, self.origin.1, self.origin.2);
+        turtle_copy_region(self.span.0, self.span.1, self.span.2);
+    }
+
+    /// Paste the previously copied region into this region using [`turtle_paste_region_masked()`],
+    /// ignoring air blocks.
+    ///
+    /// This moves the turtle to the origin of the region.
+    #[inline(always)]
+    pub fn paste_masked(&self) {
+        turtle_pos(self.origin.0, self.origin.1, self.origin.2);
+        turtle_paste_region_masked(self.span.0, self.span.1, self.span.2);
+    }
+}
+
+impl IntoIterator for &Region {
+    type Item = (i32, i32, i32);
+    type IntoIter = RegionIter;
+
+    #[inline]
+    fn into_iter(self) -> RegionIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the positions in a [`Region`], created by [`Region::iter`].
+#[derive(Clone, Debug)]
+pub struct RegionIter {
+    region: Region,
+    next: Option<(i32, i32, i32)>,
+}
+
+impl Iterator for RegionIter {
+    type Item = (i32, i32, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        let (min, max) = (self.region.min(), self.region.max());
+        let (mut x, mut y, mut z) = current;
+        x += 1;
+        if x > max.0 {
+            x = min.0;
+            z += 1;
+            if z > max.2 {
+                z = min.2;
+                y += 1;
+            }
+        }
+        self.next = if y > max.1 { None } else { Some((x, y, z)) };
+        Some(current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = match self.next {
+            Some((x, y, z)) => {
+                let (max, size) = (self.region.max(), self.region.size());
+                let layers = (max.1 - y) as usize * size.0 as usize * size.2 as usize;
+                let rows = (max.2 - z) as usize * size.0 as usize;
+                layers + rows + (max.0 - x) as usize + 1
+            }
+            None => 0,
+        };
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for RegionIter {}
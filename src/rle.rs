@@ -0,0 +1,134 @@
+//! Run-length encoding for block data.
+//!
+//! Structures often contain long runs of the same block, so storing them as [`Run`]s can use far less
+//! memory than storing every block. Each run takes up 2 bytes, compared to 4 bytes for a single
+//! [`Block`].
+//!
+//! Run-length encoded data can be placed into the world directly with [`paste_rle`], without needing
+//! to decode it into a buffer first.
+
+use crate::region::Region;
+use crate::scan::{walk, ScanOrder};
+use crate::{turtle_set, Block};
+
+/// A run of between 1 and [`Run::MAX_COUNT`] identical blocks.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Run(u16);
+
+impl Run {
+    /// The maximum number of blocks in a single run.
+    pub const MAX_COUNT: usize = 4096;
+
+    /// Create a run of `count` copies of `block`.
+    ///
+    /// Panics if `count` is 0 or greater than [`Run::MAX_COUNT`].
+    #[inline(always)]
+    pub const fn new(block: Block, count: usize) -> Self {
+        assert!(count > 0 && count <= Self::MAX_COUNT);
+        Self((((count - 1) as u16) << 4) | block as u16)
+    }
+
+    /// The block repeated in this run.
+    #[inline(always)]
+    pub const fn block(self) -> Block {
+        match Block::from_id((self.0 & 0xf) as i32) {
+            Some(block) => block,
+            None => Block::Air,
+        }
+    }
+
+    /// The number of blocks in this run.
+    #[inline(always)]
+    pub const fn count(self) -> usize {
+        (self.0 >> 4) as usize + 1
+    }
+}
+
+/// Get the number of runs needed to encode a buffer of blocks.
+pub fn rle_len(src: &[Block]) -> usize {
+    let mut runs = 0;
+    let mut current: Option<(Block, usize)> = None;
+    for block in src {
+        current = match current {
+            Some((b, count)) if b == *block && count < Run::MAX_COUNT => Some((b, count + 1)),
+            _ => {
+                runs += 1;
+                Some((*block, 1))
+            }
+        };
+    }
+    runs
+}
+
+/// Run-length encode a buffer of blocks into `dst`.
+///
+/// Returns the number of runs written, or `None` if `dst` is too small (use [`rle_len`] to find the
+/// required size).
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{Block, rle::{rle_encode, Run}};
+/// let blocks = [Block::Air, Block::Air, Block::Air, Block::Gold];
+/// let mut runs = [Run::new(Block::Air, 1); 4];
+/// let len = rle_encode(&blocks, &mut runs).unwrap();
+/// assert_eq!(&runs[..len], &[Run::new(Block::Air, 3), Run::new(Block::Gold, 1)]);
+/// ```
+pub fn rle_encode(src: &[Block], dst: &mut [Run]) -> Option<usize> {
+    let mut len = 0;
+    let mut current: Option<(Block, usize)> = None;
+    for block in src {
+        current = match current {
+            Some((b, count)) if b == *block && count < Run::MAX_COUNT => Some((b, count + 1)),
+            Some((b, count)) => {
+                *dst.get_mut(len)? = Run::new(b, count);
+                len += 1;
+                Some((*block, 1))
+            }
+            None => Some((*block, 1)),
+        };
+    }
+    if let Some((b, count)) = current {
+        *dst.get_mut(len)? = Run::new(b, count);
+        len += 1;
+    }
+    Some(len)
+}
+
+/// Decode run-length encoded blocks into `dst`.
+///
+/// Returns the number of blocks written, or `None` if `dst` is too small.
+pub fn rle_decode(src: &[Run], dst: &mut [Block]) -> Option<usize> {
+    let mut len = 0;
+    for run in src {
+        dst.get_mut(len..len + run.count())?.fill(run.block());
+        len += run.count();
+    }
+    Some(len)
+}
+
+/// Place run-length encoded blocks into a region in [`ScanOrder::RowMajor`] order, without decoding
+/// them into a buffer first.
+///
+/// If the runs contain fewer blocks than the region, only that many blocks are placed. Returns the
+/// number of blocks placed.
+///
+/// This moves the turtle, so the turtle position is undefined after calling this function.
+pub fn paste_rle(region: &Region, runs: &[Run]) -> usize {
+    let mut runs = runs.iter();
+    let mut current = runs.next().map(|run| (run.block(), run.count()));
+    let mut count = 0;
+    walk(region, ScanOrder::RowMajor, |_, _| match current {
+        Some((block, remaining)) => {
+            turtle_set(block);
+            count += 1;
+            current = if remaining > 1 {
+                Some((block, remaining - 1))
+            } else {
+                runs.next().map(|run| (run.block(), run.count()))
+            };
+            true
+        }
+        None => false,
+    });
+    count
+}
@@ -0,0 +1,224 @@
+//! Reading regions of the world into memory.
+//!
+//! Contains [`scan_region`], which reads every block in a [`Region`] into a buffer using
+//! [`turtle_get()`]. The layout of the buffer is given by a [`ScanOrder`].
+//!
+//! Buffers can be placed back into the world with [`place_region`].
+//!
+//! Regions can also be compared against expected block data without reading them into a buffer
+//! first, using [`verify_region`] and [`diff_region`]. This is useful for in-game tests of building
+//! code.
+
+use crate::region::Region;
+use crate::vec::IVec3;
+use crate::{turtle_get, turtle_set, turtle_x, turtle_y, turtle_z, Block};
+
+/// The order in which the blocks of a region are stored in a buffer.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Default)]
+pub enum ScanOrder {
+    /// x changes fastest, then z, then y, so the region is stored as horizontal layers from the
+    /// bottom up, each made of rows along the x axis. This is the same order as
+    /// [`Region::iter`] and [`Schematic`](crate::schematic::Schematic).
+    #[default]
+    RowMajor,
+    /// y changes fastest, then z, then x, so the region is stored as vertical columns of blocks from
+    /// bottom to top.
+    ColumnMajor,
+}
+
+impl ScanOrder {
+    /// Get the index in a buffer of the block at a given position in a region, or `None` if the
+    /// position is outside the region.
+    pub fn index(self, region: &Region, pos: impl Into<IVec3>) -> Option<usize> {
+        let pos = pos.into();
+        if !region.contains(pos) {
+            return None;
+        }
+        let (x, y, z) = (
+            (pos.x - region.origin.0) as usize,
+            (pos.y - region.origin.1) as usize,
+            (pos.z - region.origin.2) as usize,
+        );
+        let (size_x, size_y, size_z) = region.size();
+        Some(match self {
+            ScanOrder::RowMajor => (y * size_z as usize + z) * size_x as usize + x,
+            ScanOrder::ColumnMajor => (x * size_z as usize + z) * size_y as usize + y,
+        })
+    }
+
+    /// Get the position of the block at a given index in a buffer holding a region, or `None` if the
+    /// index is outside the region.
+    pub fn position(self, region: &Region, index: usize) -> Option<(i32, i32, i32)> {
+        if index >= region.volume() {
+            return None;
+        }
+        let (size_x, size_y, size_z) = region.size();
+        let (x, y, z) = match self {
+            ScanOrder::RowMajor => {
+                let (layer, rest) = (
+                    index / (size_x * size_z) as usize,
+                    index % (size_x * size_z) as usize,
+                );
+                (rest % size_x as usize, layer, rest / size_x as usize)
+            }
+            ScanOrder::ColumnMajor => {
+                let (column, y) = (index / size_y as usize, index % size_y as usize);
+                (column / size_z as usize, y, column % size_z as usize)
+            }
+        };
+        Some((
+            region.origin.0 + x as i32,
+            region.origin.1 + y as i32,
+            region.origin.2 + z as i32,
+        ))
+    }
+}
+
+/// Move the turtle to each position in a region in the given order, only setting the coordinates
+/// that change, and call `f` with the index and position. Stops early if `f` returns `false`.
+pub(crate) fn walk(
+    region: &Region,
+    order: ScanOrder,
+    mut f: impl FnMut(usize, (i32, i32, i32)) -> bool,
+) {
+    let (min, max) = (region.min(), region.max());
+    let mut index = 0;
+    match order {
+        ScanOrder::RowMajor => {
+            for y in min.1..=max.1 {
+                turtle_y(y);
+                for z in min.2..=max.2 {
+                    turtle_z(z);
+                    for x in min.0..=max.0 {
+                        turtle_x(x);
+                        if !f(index, (x, y, z)) {
+                            return;
+                        }
+                        index += 1;
+                    }
+                }
+            }
+        }
+        ScanOrder::ColumnMajor => {
+            for x in min.0..=max.0 {
+                turtle_x(x);
+                for z in min.2..=max.2 {
+                    turtle_z(z);
+                    for y in min.1..=max.1 {
+                        turtle_y(y);
+                        if !f(index, (x, y, z)) {
+                            return;
+                        }
+                        index += 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Read the blocks in a region into a buffer, in the given order.
+///
+/// If the buffer is smaller than the region, only the first `buf.len()` blocks are read. Returns the
+/// number of blocks read.
+///
+/// This moves the turtle, so the turtle position is undefined after calling this function.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{Block, region::Region, scan::{scan_region, ScanOrder}};
+/// let region = Region::from_size((0, 64, 0), (8, 1, 8));
+/// let mut cells = [Block::Air; 64];
+/// scan_region(&region, &mut cells, ScanOrder::RowMajor);
+/// ```
+pub fn scan_region(region: &Region, buf: &mut [Block], order: ScanOrder) -> usize {
+    let mut count = 0;
+    walk(region, order, |index, _| match buf.get_mut(index) {
+        Some(block) => {
+            *block = turtle_get();
+            count += 1;
+            true
+        }
+        None => false,
+    });
+    count
+}
+
+/// Place the blocks in a buffer into a region, in the given order. This is the inverse of
+/// [`scan_region`].
+///
+/// If the buffer is smaller than the region, only the first `buf.len()` blocks are placed. Returns
+/// the number of blocks placed.
+///
+/// This moves the turtle, so the turtle position is undefined after calling this function.
+pub fn place_region(region: &Region, buf: &[Block], order: ScanOrder) -> usize {
+    let mut count = 0;
+    walk(region, order, |index, _| match buf.get(index) {
+        Some(block) => {
+            turtle_set(*block);
+            count += 1;
+            true
+        }
+        None => false,
+    });
+    count
+}
+
+/// Compare the blocks in a region against expected block data in [`ScanOrder::RowMajor`] order,
+/// returning the position of the first block that does not match, or `None` if every block matches.
+///
+/// If `expected` is smaller than the region, only the first `expected.len()` blocks are compared.
+///
+/// This moves the turtle, so the turtle position is undefined after calling this function.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{println, region::Region, scan::verify_region};
+/// build_house();
+/// if let Some((x, y, z)) = verify_region(&HOUSE_REGION, &HOUSE_BLOCKS) {
+///     println!("house is wrong at {x} {y} {z}");
+/// }
+/// ```
+pub fn verify_region(region: &Region, expected: &[Block]) -> Option<(i32, i32, i32)> {
+    let mut mismatch = None;
+    walk(region, ScanOrder::RowMajor, |index, pos| match expected.get(index) {
+        Some(block) if turtle_get() != *block => {
+            mismatch = Some(pos);
+            false
+        }
+        Some(_) => true,
+        None => false,
+    });
+    mismatch
+}
+
+/// Compare the blocks in a region against expected block data in [`ScanOrder::RowMajor`] order,
+/// calling `on_mismatch` with the position, the expected block and the actual block for every block
+/// that does not match. Returns the number of mismatches.
+///
+/// If `expected` is smaller than the region, only the first `expected.len()` blocks are compared.
+///
+/// This moves the turtle, so the turtle position is undefined after calling this function. The
+/// callback may move the turtle freely.
+pub fn diff_region(
+    region: &Region,
+    expected: &[Block],
+    mut on_mismatch: impl FnMut((i32, i32, i32), Block, Block),
+) -> usize {
+    let mut mismatches = 0;
+    walk(region, ScanOrder::RowMajor, |index, (x, y, z)| match expected.get(index) {
+        Some(block) => {
+            let actual = turtle_get();
+            if actual != *block {
+                mismatches += 1;
+                on_mismatch((x, y, z), *block, actual);
+                // The callback may have moved the turtle
+                turtle_y(y);
+                turtle_z(z);
+            }
+            true
+        }
+        None => false,
+    });
+    mismatches
+}
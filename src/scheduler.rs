@@ -0,0 +1,128 @@
+//! Running callbacks periodically.
+//!
+//! Most interactive programs are a loop that does a few different things at different rates (e.g.
+//! polling [`input`](crate::input) every tick and redrawing a clock every second) and calls
+//! [`mc_sleep`] at the end of each tick. [`Scheduler`] implements this loop: register callbacks with
+//! [`Scheduler::every`], then call [`Scheduler::run`].
+
+use crate::mc_sleep;
+
+enum Callback<'a> {
+    Fn(fn()),
+    FnMut(&'a mut dyn FnMut()),
+}
+
+struct Task<'a> {
+    interval: u32,
+    /// The tick at which the callback is next called.
+    next: u32,
+    callback: Callback<'a>,
+}
+
+/// Runs up to `N` callbacks, each at a fixed interval in game ticks.
+///
+/// Callbacks are called on the first tick after they are registered and then every `interval` ticks.
+/// Callbacks that are due on the same tick are called in the order they were registered, and
+/// [`mc_sleep`] is called once all of them have returned.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::scheduler::Scheduler;
+/// fn draw_clock() {
+///     // ...
+/// }
+///
+/// let mut frames = 0;
+/// let mut count_frames = || frames += 1;
+/// let mut scheduler: Scheduler<4> = Scheduler::new();
+/// scheduler.every(20, draw_clock).unwrap();
+/// scheduler.every_mut(1, &mut count_frames).unwrap();
+/// scheduler.run();
+/// ```
+pub struct Scheduler<'a, const N: usize> {
+    tasks: [Option<Task<'a>>; N],
+    tick: u32,
+}
+
+impl<'a, const N: usize> Scheduler<'a, N> {
+    const EMPTY: Option<Task<'a>> = None;
+
+    /// Create a scheduler with no callbacks.
+    pub const fn new() -> Self {
+        Self {
+            tasks: [Self::EMPTY; N],
+            tick: 0,
+        }
+    }
+
+    fn add(&mut self, interval: u32, callback: Callback<'a>) -> Option<usize> {
+        let id = self.tasks.iter().position(Option::is_none)?;
+        self.tasks[id] = Some(Task {
+            interval: interval.max(1),
+            next: self.tick,
+            callback,
+        });
+        Some(id)
+    }
+
+    /// Call a function every `interval` ticks (an interval of 0 is treated as 1), returning its ID, or
+    /// `None` if `N` callbacks are already registered.
+    pub fn every(&mut self, interval: u32, f: fn()) -> Option<usize> {
+        self.add(interval, Callback::Fn(f))
+    }
+
+    /// Call a closure every `interval` ticks (an interval of 0 is treated as 1), returning its ID, or
+    /// `None` if `N` callbacks are already registered.
+    pub fn every_mut(&mut self, interval: u32, f: &'a mut dyn FnMut()) -> Option<usize> {
+        self.add(interval, Callback::FnMut(f))
+    }
+
+    /// Stop calling a callback. Its ID may be reused by a callback registered later.
+    pub fn cancel(&mut self, id: usize) {
+        if let Some(task) = self.tasks.get_mut(id) {
+            *task = None;
+        }
+    }
+
+    /// The number of ticks run so far.
+    #[inline(always)]
+    pub fn ticks(&self) -> u32 {
+        self.tick
+    }
+
+    /// Call every callback that is due this tick, then call [`mc_sleep`].
+    pub fn run_tick(&mut self) {
+        let tick = self.tick;
+        for task in self.tasks.iter_mut().flatten() {
+            if (tick.wrapping_sub(task.next) as i32) >= 0 {
+                task.next = tick.wrapping_add(task.interval);
+                match &mut task.callback {
+                    Callback::Fn(f) => f(),
+                    Callback::FnMut(f) => f(),
+                }
+            }
+        }
+        self.tick = tick.wrapping_add(1);
+        mc_sleep();
+    }
+
+    /// Run the given number of ticks.
+    pub fn run_for(&mut self, ticks: u32) {
+        for _ in 0..ticks {
+            self.run_tick();
+        }
+    }
+
+    /// Run forever.
+    pub fn run(&mut self) -> ! {
+        loop {
+            self.run_tick();
+        }
+    }
+}
+
+impl<const N: usize> Default for Scheduler<'_, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
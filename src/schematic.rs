@@ -0,0 +1,150 @@
+//! 3D block structures.
+//!
+//! Contains [`Schematic`], a structure of blocks which can be pasted at the turtle position. With the
+//! `schematic` feature enabled, schematics can be created from structure files at compile time using
+//! [`include_schematic!`](crate::include_schematic).
+//!
+//! Small structures can also be written directly in source code using the [`blocks!`](crate::blocks)
+//! macro, and built with [`build_layer`] and [`build`].
+
+use crate::{turtle_pos, turtle_position, turtle_set, turtle_x, turtle_y, turtle_z, Block};
+
+/// A structure made of blocks, `width` blocks along the x axis, `height` blocks along the y axis and
+/// `length` blocks along the z axis.
+///
+/// Blocks are stored in YZX order, i.e. the block at `(x, y, z)` relative to the minimum corner of
+/// the structure is at index `(y * length + z) * width + x`.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Schematic {
+    pub width: usize,
+    pub height: usize,
+    pub length: usize,
+    pub blocks: &'static [Block],
+}
+
+impl Schematic {
+    /// Get the block at the given position relative to the minimum corner of the structure, or
+    /// `None` if the position is outside the structure.
+    pub fn get(&self, x: usize, y: usize, z: usize) -> Option<Block> {
+        if x < self.width && y < self.height && z < self.length {
+            self.blocks.get((y * self.length + z) * self.width + x).copied()
+        } else {
+            None
+        }
+    }
+
+    /// Place the structure with its minimum corner at the turtle position, building it layer by
+    /// layer from the bottom up.
+    ///
+    /// The turtle is returned to its starting position afterwards.
+    pub fn paste_at_turtle(&self) {
+        let (x, y, z) = turtle_position();
+        let mut blocks = self.blocks.iter();
+        for dy in 0..self.height {
+            turtle_y(y + dy as i32);
+            for dz in 0..self.length {
+                turtle_z(z + dz as i32);
+                for dx in 0..self.width {
+                    if let Some(block) = blocks.next() {
+                        turtle_x(x + dx as i32);
+                        turtle_set(*block);
+                    }
+                }
+            }
+        }
+        turtle_pos(x, y, z);
+    }
+}
+
+/// Place a horizontal layer of blocks with its minimum corner at the turtle position. Each row of
+/// the layer runs along the x axis, and successive rows are placed towards positive z.
+///
+/// The turtle is returned to its starting position afterwards.
+pub fn build_layer<const W: usize, const D: usize>(layer: &[[Block; W]; D]) {
+    let (x, y, z) = turtle_position();
+    for (row_index, row) in layer.iter().enumerate() {
+        turtle_z(z + row_index as i32);
+        for (column, block) in row.iter().enumerate() {
+            turtle_x(x + column as i32);
+            turtle_set(*block);
+        }
+    }
+    turtle_pos(x, y, z);
+}
+
+/// Place a structure made of horizontal layers with its minimum corner at the turtle position,
+/// building it layer by layer from the bottom up. See [`build_layer`] for the layout of each layer.
+///
+/// The turtle is returned to its starting position afterwards.
+pub fn build<const W: usize, const D: usize, const H: usize>(layers: &[[[Block; W]; D]; H]) {
+    let (x, y, z) = turtle_position();
+    for (layer_index, layer) in layers.iter().enumerate() {
+        turtle_y(y + layer_index as i32);
+        build_layer(layer);
+    }
+    turtle_pos(x, y, z);
+}
+
+/// Implementation detail of [`blocks!`](crate::blocks): get the character used by a key, which is
+/// either an identifier or a character literal.
+#[doc(hidden)]
+pub const fn __blocks_key(key: &str) -> u8 {
+    match key.as_bytes() {
+        [c] => *c,
+        [b'\'', c, b'\''] => *c,
+        _ => panic!("blocks! keys must be single characters"),
+    }
+}
+
+/// Implementation detail of [`blocks!`](crate::blocks): convert rows of characters into a layer of
+/// blocks.
+#[doc(hidden)]
+pub const fn __blocks_layer<const W: usize, const D: usize>(
+    rows: &[&str],
+    keys: &[(u8, Block)],
+) -> [[Block; W]; D] {
+    let mut layer = [[Block::Air; W]; D];
+    if rows.len() != D {
+        panic!("blocks! layers must all have the same number of rows");
+    }
+    let mut z = 0;
+    while z < D {
+        let row = rows[z].as_bytes();
+        if row.len() != W {
+            panic!("blocks! rows must all have the same length");
+        }
+        let mut x = 0;
+        while x < W {
+            let mut i = 0;
+            loop {
+                if i == keys.len() {
+                    panic!("blocks! grid contains a character with no block assigned");
+                }
+                if keys[i].0 == row[x] {
+                    layer[z][x] = keys[i].1;
+                    break;
+                }
+                i += 1;
+            }
+            x += 1;
+        }
+        z += 1;
+    }
+    layer
+}
+
+/// Implementation detail of [`blocks!`](crate::blocks): convert layers of rows of characters into a
+/// 3D structure.
+#[doc(hidden)]
+pub const fn __blocks_layers<const W: usize, const D: usize, const H: usize>(
+    layers: &[&[&str]],
+    keys: &[(u8, Block)],
+) -> [[[Block; W]; D]; H] {
+    let mut structure = [[[Block::Air; W]; D]; H];
+    let mut y = 0;
+    while y < H {
+        structure[y] = __blocks_layer(layers[y], keys);
+        y += 1;
+    }
+    structure
+}
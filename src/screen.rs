@@ -0,0 +1,234 @@
+//! A framebuffer for drawing 2D images out of blocks.
+//!
+//! Contains [`Screen`], which keeps a copy of its contents in WASM memory and only places the blocks
+//! that have changed since the last [`Screen::flush`]. Since every block placed costs commands,
+//! drawing into a [`Screen`] and flushing once per frame is usually much faster than placing blocks
+//! directly.
+//!
+//! With the `embedded-graphics` feature enabled, [`Screen`] implements
+//! [`DrawTarget`](embedded_graphics_core::draw_target::DrawTarget), so the text, primitives and
+//! images from the [`embedded-graphics`](https://docs.rs/embedded-graphics/latest/embedded_graphics/)
+//! ecosystem can be drawn onto it using [`Block`]s as colours.
+
+use crate::mem::{copy_slice, fill_raw, move_slice};
+use crate::{text, turtle_set, turtle_x, turtle_y, turtle_z, Block};
+use core::mem::size_of_val;
+
+/// A `W` by `H` block framebuffer on a vertical wall in the XY plane.
+///
+/// Pixel coordinates start at the top left corner of the screen, with x increasing towards positive
+/// x and y increasing downwards (towards negative y in the world), matching the usual convention for
+/// displays.
+///
+/// Each pixel takes up 8 bytes of memory (the drawn and the displayed block), so large screens may
+/// require increasing the stack size or declaring the screen as a `static`.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{Block, screen::Screen};
+/// let mut screen: Screen<16, 16> = Screen::new(0, 0, 0);
+/// screen.set(3, 4, Block::Gold);
+/// screen.flush();
+/// ```
+pub struct Screen<const W: usize, const H: usize> {
+    x: i32,
+    y: i32,
+    z: i32,
+    buffer: [[Block; W]; H],
+    shown: [[Block; W]; H],
+}
+
+impl<const W: usize, const H: usize> Screen<W, H> {
+    /// Create a new screen whose bottom left corner is at the given world position.
+    ///
+    /// The screen assumes that its area of the world is currently filled with air. If this is not
+    /// the case, call [`Screen::invalidate`] before the first [`Screen::flush`].
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            buffer: [[Block::Air; W]; H],
+            shown: [[Block::Air; W]; H],
+        }
+    }
+
+    /// The width of the screen in blocks.
+    #[inline(always)]
+    pub const fn width(&self) -> usize {
+        W
+    }
+
+    /// The height of the screen in blocks.
+    #[inline(always)]
+    pub const fn height(&self) -> usize {
+        H
+    }
+
+    /// The world position of the bottom left corner of the screen.
+    #[inline(always)]
+    pub const fn origin(&self) -> (i32, i32, i32) {
+        (self.x, self.y, self.z)
+    }
+
+    /// Get the block drawn at the given pixel, or `None` if the pixel is outside the screen.
+    ///
+    /// This returns the contents of the framebuffer, which may not have been flushed to the world yet.
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<Block> {
+        self.buffer.get(y).and_then(|row| row.get(x)).copied()
+    }
+
+    /// Draw a block at the given pixel. Pixels outside the screen are ignored.
+    #[inline]
+    pub fn set(&mut self, x: usize, y: usize, block: Block) {
+        if let Some(pixel) = self.buffer.get_mut(y).and_then(|row| row.get_mut(x)) {
+            *pixel = block;
+        }
+    }
+
+    /// Fill the whole framebuffer with a single block.
+    pub fn clear(&mut self, block: Block) {
+        if block == Block::Air {
+            // SAFETY: `Block` is `repr(C)` and `Block::Air` is 0, so a zeroed buffer is all air
+            unsafe {
+                fill_raw(self.buffer.as_mut_ptr() as *mut u8, 0, size_of_val(&self.buffer));
+            }
+            return;
+        }
+        for row in self.buffer.iter_mut() {
+            row.fill(block);
+        }
+    }
+
+    /// Fill a rectangle of the framebuffer with a single block. Any part of the rectangle outside
+    /// the screen is ignored.
+    pub fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, block: Block) {
+        for row in self.buffer.iter_mut().skip(y).take(height) {
+            for pixel in row.iter_mut().skip(x).take(width) {
+                *pixel = block;
+            }
+        }
+    }
+
+    /// Move the contents of the framebuffer up by `rows` pixels, discarding the rows that move off
+    /// the top and filling the rows uncovered at the bottom with `fill`.
+    pub fn scroll_up(&mut self, rows: usize, fill: Block) {
+        let rows = rows.min(H);
+        move_slice(&mut self.buffer, rows..H, 0);
+        for row in self.buffer[H - rows..].iter_mut() {
+            row.fill(fill);
+        }
+    }
+
+    /// Draw text onto the screen using the built-in [font](crate::text), with the top left corner of
+    /// the first character at the given pixel.
+    ///
+    /// Each line (separated by `'\n'`) is drawn [`LINE_HEIGHT`](crate::text::LINE_HEIGHT) pixels
+    /// below the previous one. Pixels of the glyphs are drawn with `block`. If `background` is `Some`,
+    /// the remaining pixels of each glyph are filled with that block, otherwise they are left
+    /// unchanged. Any part of the text outside the screen is ignored.
+    pub fn draw_text(&mut self, x: usize, y: usize, s: &str, block: Block, background: Option<Block>) {
+        for (line_index, line) in s.split('\n').enumerate() {
+            for (char_index, c) in line.chars().enumerate() {
+                for (row, bits) in text::glyph(c).iter().enumerate() {
+                    for column in 0..text::GLYPH_WIDTH {
+                        let pixel = if text::glyph_pixel(*bits, column) { Some(block) } else { background };
+                        if let Some(pixel) = pixel {
+                            self.set(
+                                x + char_index * text::GLYPH_WIDTH + column,
+                                y + line_index * text::LINE_HEIGHT + row,
+                                pixel,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Mark the whole screen as out of date, so that every pixel is placed on the next
+    /// [`Screen::flush`] regardless of whether it has changed.
+    pub fn invalidate(&mut self) {
+        for (row, shown) in self.buffer.iter().zip(self.shown.iter_mut()) {
+            for (pixel, shown) in row.iter().zip(shown.iter_mut()) {
+                // Any block other than the one in the framebuffer will do
+                *shown = if *pixel == Block::Air { Block::Cobblestone } else { Block::Air };
+            }
+        }
+    }
+
+    /// Place every pixel that has changed since the last flush into the world.
+    ///
+    /// This moves the turtle, so the turtle position is undefined after calling this function.
+    pub fn flush(&mut self) {
+        turtle_z(self.z);
+        for (row_index, (row, shown)) in self.buffer.iter().zip(self.shown.iter_mut()).enumerate() {
+            if row == shown {
+                continue;
+            }
+            turtle_y(self.y + (H - 1 - row_index) as i32);
+            for (column, (pixel, shown)) in row.iter().zip(shown.iter()).enumerate() {
+                if pixel != shown {
+                    turtle_x(self.x + column as i32);
+                    turtle_set(*pixel);
+                }
+            }
+            copy_slice(shown, row);
+        }
+    }
+}
+
+#[cfg(feature = "embedded-graphics")]
+mod eg {
+    use super::Screen;
+    use crate::Block;
+    use core::convert::Infallible;
+    use embedded_graphics_core::draw_target::DrawTarget;
+    use embedded_graphics_core::geometry::{Dimensions, OriginDimensions, Size};
+    use embedded_graphics_core::pixelcolor::PixelColor;
+    use embedded_graphics_core::primitives::Rectangle;
+    use embedded_graphics_core::Pixel;
+
+    impl PixelColor for Block {
+        type Raw = ();
+    }
+
+    impl<const W: usize, const H: usize> OriginDimensions for Screen<W, H> {
+        fn size(&self) -> Size {
+            Size::new(W as u32, H as u32)
+        }
+    }
+
+    impl<const W: usize, const H: usize> DrawTarget for Screen<W, H> {
+        type Color = Block;
+        type Error = Infallible;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            for Pixel(point, block) in pixels {
+                if point.x >= 0 && point.y >= 0 {
+                    self.set(point.x as usize, point.y as usize, block);
+                }
+            }
+            Ok(())
+        }
+
+        fn fill_solid(&mut self, area: &Rectangle, block: Self::Color) -> Result<(), Self::Error> {
+            let area = area.intersection(&self.bounding_box());
+            if let Some(bottom_right) = area.bottom_right() {
+                let width = (bottom_right.x - area.top_left.x + 1) as usize;
+                let height = (bottom_right.y - area.top_left.y + 1) as usize;
+                self.fill_rect(area.top_left.x as usize, area.top_left.y as usize, width, height, block);
+            }
+            Ok(())
+        }
+
+        fn clear(&mut self, block: Self::Color) -> Result<(), Self::Error> {
+            Screen::clear(self, block);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//! Seven-segment displays built out of blocks.
+//!
+//! Contains [`SevenSegment`], which shows a number on a row of seven-segment digits in the world,
+//! for in-world counters and clocks. Each segment is a straight line of blocks placed with a single
+//! [`turtle_fill`], and updating the display only places the segments that have turned on or off,
+//! so a counter that goes up by one usually costs only a few fills.
+//!
+//! Segments are stored as bit masks, with bits 0 to 6 being the segments `a` to `g` in the usual
+//! order: `a` is the top, then clockwise `b` (top right), `c` (bottom right), `d` (bottom), `e`
+//! (bottom left), `f` (top left), and `g` is the middle.
+
+use crate::{turtle_fill, turtle_pos, Block};
+
+/// The segments of the hexadecimal digits `0` to `F`.
+pub const DIGITS: [u8; 16] = [
+    0b0111111, // 0
+    0b0000110, // 1
+    0b1011011, // 2
+    0b1001111, // 3
+    0b1100110, // 4
+    0b1101101, // 5
+    0b1111101, // 6
+    0b0000111, // 7
+    0b1111111, // 8
+    0b1101111, // 9
+    0b1110111, // A
+    0b1111100, // b
+    0b0111001, // C
+    0b1011110, // d
+    0b1111001, // E
+    0b1110001, // F
+];
+
+/// The segments of a minus sign.
+pub const MINUS: u8 = 0b1000000;
+
+/// A row of `D` seven-segment digits on a vertical wall in the XY plane.
+///
+/// Each segment is `size` blocks long and one block thick, so each digit is `size + 2` blocks wide
+/// and `2 * size + 3` blocks tall. Digits are placed towards positive x, with a gap of one block
+/// between them by default.
+///
+/// The display assumes that its area of the world is currently filled with its background block
+/// (air by default). If this is not the case, call [`SevenSegment::invalidate`] before the first
+/// update.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{Block, segment::SevenSegment};
+/// let mut counter: SevenSegment<4> = SevenSegment::new(0, 100, 0, 3, Block::Redstone);
+/// for tick in 0.. {
+///     counter.update(tick);
+///     mcinterface::mc_sleep();
+/// }
+/// ```
+pub struct SevenSegment<const D: usize> {
+    x: i32,
+    y: i32,
+    z: i32,
+    size: i32,
+    spacing: i32,
+    block: Block,
+    background: Block,
+    leading_zeros: bool,
+    shown: [u8; D],
+    /// Whether every segment should be placed on the next update.
+    invalid: bool,
+}
+
+impl<const D: usize> SevenSegment<D> {
+    /// Create a display whose top left corner is at the given world position, with segments `size`
+    /// blocks long (at least 1) made of `block`.
+    pub const fn new(x: i32, y: i32, z: i32, size: i32, block: Block) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            size: if size < 1 { 1 } else { size },
+            spacing: 1,
+            block,
+            background: Block::Air,
+            leading_zeros: false,
+            shown: [0; D],
+            invalid: false,
+        }
+    }
+
+    /// Set the block placed where a segment is off.
+    pub const fn background(mut self, background: Block) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Set the gap in blocks between digits.
+    pub const fn spacing(mut self, spacing: i32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Set whether numbers are padded with zeros to fill every digit, like a clock.
+    pub const fn leading_zeros(mut self, leading_zeros: bool) -> Self {
+        self.leading_zeros = leading_zeros;
+        self
+    }
+
+    /// The width of one digit in blocks.
+    #[inline(always)]
+    pub const fn digit_width(&self) -> i32 {
+        self.size + 2
+    }
+
+    /// The total width of the display in blocks.
+    #[inline]
+    pub const fn width(&self) -> i32 {
+        D as i32 * (self.digit_width() + self.spacing) - self.spacing
+    }
+
+    /// The height of the display in blocks.
+    #[inline(always)]
+    pub const fn height(&self) -> i32 {
+        2 * self.size + 3
+    }
+
+    /// The segments currently shown by each digit.
+    #[inline(always)]
+    pub fn segments(&self) -> &[u8; D] {
+        &self.shown
+    }
+
+    /// Show a number in decimal, right-aligned, with a minus sign before negative numbers. If the
+    /// number has more than `D` digits, only the lowest `D` digits are shown, and if there is no room
+    /// for the minus sign, it replaces the leftmost digit.
+    ///
+    /// This moves the turtle, so the turtle position is undefined after calling this function.
+    pub fn update(&mut self, n: i32) {
+        if D == 0 {
+            return;
+        }
+        let mut segments = [0; D];
+        let mut value = n.unsigned_abs();
+        let mut count = 0;
+        for (i, digit) in segments.iter_mut().enumerate().rev() {
+            if value == 0 && i != D - 1 && !self.leading_zeros {
+                break;
+            }
+            *digit = DIGITS[(value % 10) as usize];
+            value /= 10;
+            count += 1;
+        }
+        if n < 0 {
+            segments[if count < D { D - 1 - count } else { 0 }] = MINUS;
+        }
+        self.update_segments(segments);
+    }
+
+    /// Show the given segments on each digit (see [`DIGITS`] and [`MINUS`]), only placing the
+    /// segments that have changed.
+    ///
+    /// This moves the turtle, so the turtle position is undefined after calling this function.
+    pub fn update_segments(&mut self, segments: [u8; D]) {
+        for (i, new) in segments.iter().enumerate() {
+            let new = new & 0b1111111;
+            let changed = if self.invalid { 0b1111111 } else { new ^ self.shown[i] };
+            for segment in 0..7 {
+                if changed & (1 << segment) != 0 {
+                    let block = if new & (1 << segment) != 0 { self.block } else { self.background };
+                    self.draw_segment(i, segment, block);
+                }
+            }
+            self.shown[i] = new;
+        }
+        self.invalid = false;
+    }
+
+    /// Turn every segment off.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.update_segments([0; D]);
+    }
+
+    /// Mark every segment as out of date, so that every segment is placed on the next update
+    /// regardless of whether it has changed.
+    #[inline(always)]
+    pub fn invalidate(&mut self) {
+        self.invalid = true;
+    }
+
+    /// Place one segment of a digit.
+    fn draw_segment(&self, digit: usize, segment: u8, block: Block) {
+        let size = self.size;
+        // Position of the segment's top left block relative to the digit, with y downwards, and its
+        // span along each axis
+        let (dx, dy, x_span, y_span) = match segment {
+            0 => (1, 0, size - 1, 0),
+            1 => (size + 1, 1, 0, size - 1),
+            2 => (size + 1, size + 2, 0, size - 1),
+            3 => (1, 2 * size + 2, size - 1, 0),
+            4 => (0, size + 2, 0, size - 1),
+            5 => (0, 1, 0, size - 1),
+            _ => (1, size + 1, size - 1, 0),
+        };
+        let x = self.x + digit as i32 * (self.digit_width() + self.spacing) + dx;
+        turtle_pos(x, self.y - dy - y_span, self.z);
+        turtle_fill(block, x_span, y_span, 0);
+    }
+}
@@ -0,0 +1,307 @@
+//! A host-side simulator for running programs without Minecraft.
+//!
+//! With the `simulator` feature enabled, the wasmcraft2 API is implemented natively against an
+//! in-memory [`World`] instead of being imported from wasmcraft2, so programs (and their unit tests)
+//! can be run on the host with `cargo test`. This feature requires `std`, and also disables the
+//! panic handler and the global allocator so that the standard library's are used instead.
+//!
+//! Each thread has its own world, and the crate's other global state (such as the
+//! [`pacing`](crate::pacing) budget, the [`panic`](crate::panic) hook and beacon and whether
+//! [`style`](crate::style) codes are enabled) is per-thread too, so tests run in parallel by
+//! `cargo test` do not interfere with each other. The world starts out filled with air; use
+//! [`with_world`] to set up blocks before running code and to inspect the blocks placed and the chat
+//! messages printed afterwards:
+//! ```ignore
+//! # use mcinterface::{simulator, turtle_pos, turtle_set, println, Block};
+//! #[test]
+//! fn builds_a_pillar() {
+//!     simulator::reset();
+//!     turtle_pos(0, 64, 0);
+//!     turtle_set(Block::Gold);
+//!     println("done");
+//!     simulator::with_world(|world| {
+//!         assert_eq!(world.get(0, 64, 0), Block::Gold);
+//!         assert_eq!(world.chat(), ["done"]);
+//!     });
+//! }
+//! ```
+//!
+//! [`mc_sleep`](crate::mc_sleep) advances the world's tick counter and calls the hook set with
+//! [`World::set_tick_hook`], which can be used to change the world while the program is running (for
+//! example to press [`input`](crate::input) buttons).
+//!
+//! With the `simulator-schem` feature enabled, [`World::save_schem`] and
+//! [`World::assert_matches_schem`] can be used to save the world as a Sponge schematic (`.schem`) and
+//! to compare it against one, for golden tests of programs that build structures.
+
+#[cfg(feature = "simulator-schem")]
+mod schem;
+
+use crate::Block;
+use std::boxed::Box;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::string::String;
+use std::vec::Vec;
+
+/// A function set with [`World::set_tick_hook`].
+type TickHook = Box<dyn FnMut(&mut World)>;
+
+/// An in-memory Minecraft world, along with the state wasmcraft2 keeps outside of the world (the
+/// turtle position and the copied block and region) and the chat output.
+#[derive(Default)]
+pub struct World {
+    blocks: HashMap<(i32, i32, i32), Block>,
+    turtle: (i32, i32, i32),
+    copied_block: Option<Block>,
+    /// The blocks copied with `turtle_copy_region`, by offset from the turtle.
+    copied_region: Vec<((i32, i32, i32), Block)>,
+    chat: Vec<String>,
+    line: String,
+    ticks: u64,
+    tick_limit: Option<u64>,
+    tick_hook: Option<TickHook>,
+}
+
+impl World {
+    /// Create an empty world.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the block at the given position.
+    pub fn get(&self, x: i32, y: i32, z: i32) -> Block {
+        self.blocks.get(&(x, y, z)).copied().unwrap_or(Block::Air)
+    }
+
+    /// Set the block at the given position.
+    pub fn set(&mut self, x: i32, y: i32, z: i32, block: Block) {
+        if block == Block::Air {
+            self.blocks.remove(&(x, y, z));
+        } else {
+            self.blocks.insert((x, y, z), block);
+        }
+    }
+
+    /// Iterate over the positions and blocks of every block other than [`Block::Air`] in the world, in
+    /// no particular order.
+    pub fn blocks(&self) -> impl Iterator<Item = ((i32, i32, i32), Block)> + '_ {
+        self.blocks.iter().map(|(&pos, &block)| (pos, block))
+    }
+
+    /// The number of blocks other than [`Block::Air`] in the world.
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// The current position of the turtle.
+    pub fn turtle(&self) -> (i32, i32, i32) {
+        self.turtle
+    }
+
+    /// The lines printed to chat so far. A line printed with [`mc_putc`](crate::mc_putc) only appears
+    /// once a newline has been written.
+    pub fn chat(&self) -> &[String] {
+        &self.chat
+    }
+
+    /// Remove and return the lines printed to chat so far.
+    pub fn take_chat(&mut self) -> Vec<String> {
+        core::mem::take(&mut self.chat)
+    }
+
+    /// The number of times [`mc_sleep`](crate::mc_sleep) has been called.
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Panic once [`mc_sleep`](crate::mc_sleep) has been called more than `limit` times, so that a
+    /// program that never finishes makes a test fail instead of hanging.
+    pub fn set_tick_limit(&mut self, limit: Option<u64>) {
+        self.tick_limit = limit;
+    }
+
+    /// Set a function to be called every time [`mc_sleep`](crate::mc_sleep) is called, after the tick
+    /// counter has been advanced. Replaces any previously set hook.
+    ///
+    /// The hook should change the world through the [`World`] it is given; calling the wasmcraft2 API
+    /// functions from inside it panics.
+    pub fn set_tick_hook(&mut self, hook: impl FnMut(&mut World) + 'static) {
+        self.tick_hook = Some(Box::new(hook));
+    }
+
+    /// Remove the hook set with [`set_tick_hook`](World::set_tick_hook).
+    pub fn clear_tick_hook(&mut self) {
+        self.tick_hook = None;
+    }
+
+    fn fill(&mut self, block: Block, x_span: i32, y_span: i32, z_span: i32) {
+        let (x, y, z) = self.turtle;
+        for dy in span_range(y_span) {
+            for dz in span_range(z_span) {
+                for dx in span_range(x_span) {
+                    self.set(x + dx, y + dy, z + dz, block);
+                }
+            }
+        }
+    }
+
+    fn copy_region(&mut self, x_span: i32, y_span: i32, z_span: i32) {
+        let (x, y, z) = self.turtle;
+        self.copied_region.clear();
+        for dy in span_range(y_span) {
+            for dz in span_range(z_span) {
+                for dx in span_range(x_span) {
+                    let block = self.get(x + dx, y + dy, z + dz);
+                    self.copied_region.push(((dx, dy, dz), block));
+                }
+            }
+        }
+    }
+
+    fn paste_region_masked(&mut self, x_span: i32, y_span: i32, z_span: i32) {
+        let (x, y, z) = self.turtle;
+        let region = core::mem::take(&mut self.copied_region);
+        for &((dx, dy, dz), block) in &region {
+            let inside = span_range(x_span).contains(&dx)
+                && span_range(y_span).contains(&dy)
+                && span_range(z_span).contains(&dz);
+            if inside && block != Block::Air {
+                self.set(x + dx, y + dy, z + dz, block);
+            }
+        }
+        self.copied_region = region;
+    }
+
+    fn putc(&mut self, ch: i32) {
+        match u8::try_from(ch) {
+            Ok(b'\n') => self.chat.push(core::mem::take(&mut self.line)),
+            Ok(c @ b' '..=b'~') => self.line.push(c as char),
+            _ => self.line.push('\u{fffd}'),
+        }
+    }
+
+    fn sleep(&mut self) {
+        self.ticks += 1;
+        if let Some(limit) = self.tick_limit {
+            if self.ticks > limit {
+                panic!("simulator tick limit of {limit} exceeded");
+            }
+        }
+        if let Some(mut hook) = self.tick_hook.take() {
+            hook(self);
+            self.tick_hook.get_or_insert(hook);
+        }
+    }
+}
+
+/// The offsets covered by a span, which may be negative.
+fn span_range(span: i32) -> core::ops::RangeInclusive<i32> {
+    span.min(0)..=span.max(0)
+}
+
+std::thread_local! {
+    static WORLD: RefCell<World> = RefCell::new(World::new());
+}
+
+/// Run a function with the current thread's world.
+///
+/// Calling any of the wasmcraft2 API functions from inside the function panics.
+pub fn with_world<R>(f: impl FnOnce(&mut World) -> R) -> R {
+    WORLD.with(|world| f(&mut world.borrow_mut()))
+}
+
+/// Replace the current thread's world with an empty one.
+pub fn reset() {
+    with_world(|world| *world = World::new());
+}
+
+/// Native implementations of the functions imported from wasmcraft2.
+pub(crate) mod ffi {
+    use super::with_world;
+    use crate::Block;
+    use std::string::ToString;
+
+    pub(crate) unsafe fn _mci_unsafe_print(value: i32) {
+        with_world(|world| world.chat.push(value.to_string()));
+    }
+
+    /// Set all bytes in a region of memory (with length `length`, starting from `ptr`) to `value`.
+    ///
+    /// Behaves like C `memset()`: `length` is in bytes and only the lowest 8 bits of `value` are
+    /// used. The [`mem`](crate::mem) module has safe wrappers.
+    ///
+    /// # Safety
+    /// `ptr` must be valid for writes of `length` bytes.
+    pub unsafe fn memset(ptr: *mut i32, value: i32, length: u32) -> *mut i32 {
+        core::ptr::write_bytes(ptr as *mut u8, value as u8, length as usize);
+        ptr
+    }
+
+    pub(crate) unsafe fn _mci_unsafe_turtle_x(value: i32) {
+        with_world(|world| world.turtle.0 = value);
+    }
+
+    pub(crate) unsafe fn _mci_unsafe_turtle_y(value: i32) {
+        with_world(|world| world.turtle.1 = value);
+    }
+
+    pub(crate) unsafe fn _mci_unsafe_turtle_z(value: i32) {
+        with_world(|world| world.turtle.2 = value);
+    }
+
+    pub(crate) unsafe fn _mci_unsafe_turtle_fill(
+        block: Block,
+        x_span: i32,
+        y_span: i32,
+        z_span: i32,
+    ) {
+        with_world(|world| world.fill(block, x_span, y_span, z_span));
+    }
+
+    pub(crate) unsafe fn _mci_unsafe_turtle_set(block: Block) {
+        with_world(|world| world.fill(block, 0, 0, 0));
+    }
+
+    pub(crate) unsafe fn _mci_unsafe_turtle_get() -> Block {
+        with_world(|world| {
+            let (x, y, z) = world.turtle;
+            world.get(x, y, z)
+        })
+    }
+
+    pub(crate) unsafe fn _mci_unsafe_turtle_copy_region(x_span: i32, y_span: i32, z_span: i32) {
+        with_world(|world| world.copy_region(x_span, y_span, z_span));
+    }
+
+    pub(crate) unsafe fn _mci_unsafe_turtle_paste_region_masked(
+        x_span: i32,
+        y_span: i32,
+        z_span: i32,
+    ) {
+        with_world(|world| world.paste_region_masked(x_span, y_span, z_span));
+    }
+
+    pub(crate) unsafe fn _mci_unsafe_turtle_copy() {
+        with_world(|world| {
+            let (x, y, z) = world.turtle;
+            world.copied_block = Some(world.get(x, y, z));
+        });
+    }
+
+    pub(crate) unsafe fn _mci_unsafe_turtle_paste() {
+        with_world(|world| {
+            if let Some(block) = world.copied_block {
+                world.fill(block, 0, 0, 0);
+            }
+        });
+    }
+
+    pub(crate) unsafe fn _mci_unsafe_mc_sleep() {
+        with_world(|world| world.sleep());
+    }
+
+    pub(crate) unsafe fn _mci_unsafe_mc_putc(ch: i32) {
+        with_world(|world| world.putc(ch));
+    }
+}
@@ -0,0 +1,303 @@
+//! Saving and comparing simulator worlds as Sponge schematics (`.schem`).
+
+use super::World;
+use crate::Block;
+use fastnbt::{ByteArray, IntArray, Value};
+use std::collections::HashMap;
+use std::format;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+/// The data version written to saved schematics (Minecraft 1.20.1).
+const DATA_VERSION: i32 = 3465;
+
+/// The number of differing blocks listed by [`World::assert_matches_schem`].
+const MAX_REPORTED: usize = 10;
+
+/// The ID of a block in Minecraft.
+fn block_name(block: Block) -> &'static str {
+    match block {
+        Block::Air => "minecraft:air",
+        Block::Cobblestone => "minecraft:cobblestone",
+        Block::Granite => "minecraft:granite",
+        Block::Andesite => "minecraft:andesite",
+        Block::Diorite => "minecraft:diorite",
+        Block::Lapis => "minecraft:lapis_block",
+        Block::Iron => "minecraft:iron_block",
+        Block::Gold => "minecraft:gold_block",
+        Block::Diamond => "minecraft:diamond_block",
+        Block::Redstone => "minecraft:redstone_block",
+        Block::Emerald => "minecraft:emerald_block",
+        Block::Dirt => "minecraft:dirt",
+        Block::OakLog => "minecraft:oak_log",
+        Block::OakLeaves => "minecraft:oak_leaves",
+    }
+}
+
+/// Find the block with the given block state (e.g. `minecraft:oak_log[axis=y]`), ignoring its
+/// properties.
+fn parse_block(state: &str) -> Option<Block> {
+    let name = state.split('[').next().unwrap_or(state);
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    (0..)
+        .map_while(Block::from_id)
+        .find(|&block| block_name(block)["minecraft:".len()..] == *name)
+        .or(matches!(name, "cave_air" | "void_air" | "structure_void").then_some(Block::Air))
+}
+
+/// A cuboid of blocks, stored in YZX order.
+struct Structure {
+    offset: (i32, i32, i32),
+    size: (i32, i32, i32),
+    blocks: Vec<Block>,
+}
+
+impl Structure {
+    fn contains(&self, (x, y, z): (i32, i32, i32)) -> bool {
+        (0..self.size.0).contains(&(x - self.offset.0))
+            && (0..self.size.1).contains(&(y - self.offset.1))
+            && (0..self.size.2).contains(&(z - self.offset.2))
+    }
+
+    fn positions(&self) -> impl Iterator<Item = (i32, i32, i32)> + '_ {
+        let (ox, oy, oz) = self.offset;
+        (0..self.size.1).flat_map(move |y| {
+            (0..self.size.2)
+                .flat_map(move |z| (0..self.size.0).map(move |x| (ox + x, oy + y, oz + z)))
+        })
+    }
+}
+
+fn get<'a>(value: &'a Value, key: &str) -> Result<&'a Value, String> {
+    match value {
+        Value::Compound(map) => map.get(key).ok_or_else(|| format!("missing tag `{key}`")),
+        _ => Err(format!("expected a compound containing `{key}`")),
+    }
+}
+
+fn get_int(value: &Value, key: &str) -> Result<i64, String> {
+    match get(value, key)? {
+        Value::Byte(n) => Ok(*n as i64),
+        Value::Short(n) => Ok(*n as i64),
+        Value::Int(n) => Ok(*n as i64),
+        Value::Long(n) => Ok(*n),
+        _ => Err(format!("expected `{key}` to be an integer")),
+    }
+}
+
+/// Load a Sponge schematic, versions 1 to 3.
+fn load(path: &Path) -> Result<Structure, String> {
+    let mut data = std::fs::read(path).map_err(|e| e.to_string())?;
+    if data.starts_with(&[0x1f, 0x8b]) {
+        let mut decompressed = Vec::new();
+        flate2::read::GzDecoder::new(&data[..])
+            .read_to_end(&mut decompressed)
+            .map_err(|e| e.to_string())?;
+        data = decompressed;
+    }
+    let root: Value = fastnbt::from_bytes(&data).map_err(|e| e.to_string())?;
+
+    // Version 3 wraps everything in a `Schematic` compound and moves the palette into `Blocks`
+    let root = get(&root, "Schematic").unwrap_or(&root);
+    let blocks = get(root, "Blocks").unwrap_or(root);
+    let size = (
+        get_int(root, "Width")? as u16 as i32,
+        get_int(root, "Height")? as u16 as i32,
+        get_int(root, "Length")? as u16 as i32,
+    );
+    let offset = match get(root, "Offset") {
+        Ok(Value::IntArray(offset)) if offset.len() == 3 => (offset[0], offset[1], offset[2]),
+        Ok(_) => return Err("expected `Offset` to be an array of 3 integers".into()),
+        Err(_) => (0, 0, 0),
+    };
+
+    let Value::Compound(palette_map) = get(blocks, "Palette")? else {
+        return Err("expected `Palette` to be a compound".into());
+    };
+    let mut palette = HashMap::new();
+    for (name, index) in palette_map {
+        let Value::Int(index) = index else {
+            return Err("expected palette indices to be integers".into());
+        };
+        let block = parse_block(name)
+            .ok_or_else(|| format!("`{name}` is not supported by wasmcraft2"))?;
+        palette.insert(*index as usize, block);
+    }
+
+    let data = match get(blocks, "BlockData").or_else(|_| get(blocks, "Data"))? {
+        Value::ByteArray(data) => data,
+        _ => return Err("expected block data to be a byte array".into()),
+    };
+    let volume = (size.0 as usize)
+        .checked_mul(size.1 as usize)
+        .and_then(|area| area.checked_mul(size.2 as usize))
+        .ok_or("the schematic is too large")?;
+    let mut structure = Structure {
+        offset,
+        size,
+        // Every block takes at least one byte, so there cannot be more blocks than bytes
+        blocks: Vec::with_capacity(volume.min(data.len())),
+    };
+    let (mut value, mut shift) = (0usize, 0);
+    for byte in data.iter().map(|b| *b as u8) {
+        if shift >= 32 {
+            return Err("palette index in block data is too long".into());
+        }
+        value |= ((byte & 0x7f) as usize) << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            if structure.blocks.len() == volume {
+                return Err("block data is longer than the size of the schematic".into());
+            }
+            let block = palette.get(&value).ok_or("palette index out of range")?;
+            structure.blocks.push(*block);
+            value = 0;
+            shift = 0;
+        }
+    }
+    if shift != 0 || structure.blocks.len() != volume {
+        return Err("block data does not match the size of the schematic".into());
+    }
+    Ok(structure)
+}
+
+impl World {
+    /// The smallest cuboid containing every block other than [`Block::Air`] in the world, or a single
+    /// air block at the origin if the world is empty.
+    fn structure(&self) -> Structure {
+        let mut positions = self.blocks.keys();
+        let (min, max) = match positions.next() {
+            Some(&first) => positions.fold((first, first), |(min, max), &(x, y, z)| {
+                (
+                    (min.0.min(x), min.1.min(y), min.2.min(z)),
+                    (max.0.max(x), max.1.max(y), max.2.max(z)),
+                )
+            }),
+            None => ((0, 0, 0), (0, 0, 0)),
+        };
+        let mut structure = Structure {
+            offset: min,
+            size: (max.0 - min.0 + 1, max.1 - min.1 + 1, max.2 - min.2 + 1),
+            blocks: Vec::new(),
+        };
+        structure.blocks = structure.positions().map(|(x, y, z)| self.get(x, y, z)).collect();
+        structure
+    }
+
+    /// Save the smallest cuboid containing every block other than [`Block::Air`] in the world as a
+    /// (version 2) Sponge schematic. The position of the cuboid is saved as the schematic's offset,
+    /// so that [`assert_matches_schem`](World::assert_matches_schem) compares the same blocks.
+    pub fn save_schem(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let structure = self.structure();
+
+        let mut palette: Vec<Block> = Vec::new();
+        let mut data = Vec::with_capacity(structure.blocks.len());
+        for &block in &structure.blocks {
+            let mut index = match palette.iter().position(|&b| b == block) {
+                Some(index) => index,
+                None => {
+                    palette.push(block);
+                    palette.len() - 1
+                }
+            };
+            while index >= 0x80 {
+                data.push(((index as u8 & 0x7f) | 0x80) as i8);
+                index >>= 7;
+            }
+            data.push(index as i8);
+        }
+        let palette_max = palette.len() as i32;
+        let palette = palette
+            .into_iter()
+            .enumerate()
+            .map(|(index, block)| (block_name(block).to_string(), Value::Int(index as i32)))
+            .collect();
+
+        let (x, y, z) = structure.offset;
+        let root = Value::Compound(HashMap::from([
+            ("Version".to_string(), Value::Int(2)),
+            ("DataVersion".to_string(), Value::Int(DATA_VERSION)),
+            ("Width".to_string(), Value::Short(structure.size.0 as i16)),
+            ("Height".to_string(), Value::Short(structure.size.1 as i16)),
+            ("Length".to_string(), Value::Short(structure.size.2 as i16)),
+            ("Offset".to_string(), Value::IntArray(IntArray::new(std::vec![x, y, z]))),
+            ("PaletteMax".to_string(), Value::Int(palette_max)),
+            ("Palette".to_string(), Value::Compound(palette)),
+            ("BlockData".to_string(), Value::ByteArray(ByteArray::new(data))),
+        ]));
+        let nbt = fastnbt::to_bytes(&root).map_err(std::io::Error::other)?;
+
+        let file = std::fs::File::create(path)?;
+        let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        encoder.write_all(&nbt)?;
+        encoder.finish()?;
+        Ok(())
+    }
+
+    /// Panic unless the world contains exactly the blocks in the given Sponge schematic, placed at the
+    /// schematic's offset: every block in the schematic must match, and there must be no blocks other
+    /// than air outside it.
+    ///
+    /// If the `MCINTERFACE_BLESS` environment variable is set, the world is saved to the file with
+    /// [`save_schem`](World::save_schem) instead, which can be used to create or update golden files.
+    ///
+    /// Usage:
+    /// ```ignore
+    /// # use mcinterface::simulator;
+    /// simulator::reset();
+    /// my_program::build_house();
+    /// simulator::with_world(|world| world.assert_matches_schem("tests/golden/house.schem"));
+    /// ```
+    #[track_caller]
+    pub fn assert_matches_schem(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        if std::env::var_os("MCINTERFACE_BLESS").is_some() {
+            if let Err(e) = self.save_schem(path) {
+                panic!("failed to save {}: {e}", path.display());
+            }
+            return;
+        }
+        let expected = match load(path) {
+            Ok(structure) => structure,
+            Err(e) => panic!(
+                "failed to load {}: {e} (set MCINTERFACE_BLESS to save the current world instead)",
+                path.display()
+            ),
+        };
+
+        let mut differences: Vec<_> = expected
+            .positions()
+            .zip(&expected.blocks)
+            .filter(|&((x, y, z), &block)| self.get(x, y, z) != block)
+            .map(|(pos, &block)| (pos, block))
+            .collect();
+        differences.extend(
+            self.blocks()
+                .filter(|&(pos, _)| !expected.contains(pos))
+                .map(|(pos, _)| (pos, Block::Air)),
+        );
+        if differences.is_empty() {
+            return;
+        }
+
+        differences.sort_by_key(|&((x, y, z), _)| (y, z, x));
+        let mut message = format!(
+            "world does not match {}: {} blocks differ",
+            path.display(),
+            differences.len()
+        );
+        for &((x, y, z), block) in differences.iter().take(MAX_REPORTED) {
+            message += &format!(
+                "\n  ({x}, {y}, {z}) is {:?}, expected {:?}",
+                self.get(x, y, z),
+                block
+            );
+        }
+        if differences.len() > MAX_REPORTED {
+            message += "\n  ...";
+        }
+        panic!("{message}");
+    }
+}
@@ -0,0 +1,93 @@
+//! 2D block images.
+//!
+//! Contains [`Sprite`], a fixed-size 2D array of blocks which can be drawn on a vertical wall at the
+//! turtle position. With the `image` feature enabled, sprites can be created from image files at
+//! compile time using [`include_image!`](crate::include_image).
+//!
+//! Sprites can be drawn with air treated as transparent, which allows moving objects to be drawn over
+//! a background without erasing it. For sprites of varying sizes, see [`blit`].
+
+use crate::{turtle_pos, turtle_position, turtle_set, turtle_x, turtle_y, Block};
+
+/// A `W` by `H` image made of blocks, stored as rows from top to bottom.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{Block, sprite::Sprite, turtle_pos};
+/// const ARROW: Sprite<3, 2> = Sprite([
+///     [Block::Air, Block::Gold, Block::Air],
+///     [Block::Gold, Block::Gold, Block::Gold],
+/// ]);
+/// turtle_pos(0, 100, 0);
+/// ARROW.draw();
+/// ```
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Sprite<const W: usize, const H: usize>(pub [[Block; W]; H]);
+
+impl<const W: usize, const H: usize> Sprite<W, H> {
+    /// The width of the sprite in blocks.
+    #[inline(always)]
+    pub const fn width(&self) -> usize {
+        W
+    }
+
+    /// The height of the sprite in blocks.
+    #[inline(always)]
+    pub const fn height(&self) -> usize {
+        H
+    }
+
+    /// Draw the sprite on a vertical wall in the XY plane, with its top left corner at the turtle
+    /// position.
+    ///
+    /// The turtle is returned to its starting position afterwards.
+    #[inline]
+    pub fn draw(&self) {
+        blit_rows(self.0.iter().map(|row| &row[..]), false);
+    }
+
+    /// Draw the sprite like [`Sprite::draw`], but skip [`Block::Air`] pixels, leaving the blocks
+    /// behind them unchanged.
+    #[inline]
+    pub fn draw_masked(&self) {
+        blit_rows(self.0.iter().map(|row| &row[..]), true);
+    }
+}
+
+/// Draw a 2D array of blocks, stored as rows from top to bottom, on a vertical wall in the XY plane
+/// with its top left corner at the turtle position. Rows do not need to be the same length.
+///
+/// If `masked` is true, [`Block::Air`] cells are skipped, leaving the blocks behind them unchanged
+/// (like [`turtle_paste_region_masked()`](crate::turtle_paste_region_masked)).
+///
+/// The turtle is returned to its starting position afterwards.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{Block, sprite::blit, turtle_pos};
+/// let ball: &[&[Block]] = &[
+///     &[Block::Air, Block::Redstone, Block::Air],
+///     &[Block::Redstone, Block::Redstone, Block::Redstone],
+///     &[Block::Air, Block::Redstone, Block::Air],
+/// ];
+/// turtle_pos(10, 70, 0);
+/// blit(ball, true);
+/// ```
+#[inline]
+pub fn blit(sprite: &[&[Block]], masked: bool) {
+    blit_rows(sprite.iter().copied(), masked);
+}
+
+fn blit_rows<'a>(rows: impl Iterator<Item = &'a [Block]>, masked: bool) {
+    let (x, y, z) = turtle_position();
+    for (row_index, row) in rows.enumerate() {
+        turtle_y(y - row_index as i32);
+        for (column, block) in row.iter().enumerate() {
+            if !(masked && *block == Block::Air) {
+                turtle_x(x + column as i32);
+                turtle_set(*block);
+            }
+        }
+    }
+    turtle_pos(x, y, z);
+}
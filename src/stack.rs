@@ -0,0 +1,100 @@
+//! Stack usage monitoring.
+//!
+//! wasmcraft2 programs usually run with a very small stack (see the [crate documentation](crate)),
+//! and a stack overflow is not detected: the stack silently runs into other memory, which tends to
+//! show up as strange behaviour much later. [`init`] fills the unused part of the stack with a known
+//! pattern at startup; afterwards, [`stack_high_water`] reports how much of the stack has ever been
+//! used, and [`check_stack!`](crate::check_stack) panics if the bottom of the stack has been
+//! reached.
+//!
+//! This module is only available when compiling for `wasm32`, since it relies on the `__stack_low`
+//! and `__stack_high` symbols defined by the WebAssembly linker.
+
+use core::ptr::addr_of;
+
+extern "C" {
+    static __stack_low: u8;
+    static __stack_high: u8;
+}
+
+/// The pattern written to every unused word of the stack by [`init`].
+const PATTERN: u32 = 0x5354_4b21;
+
+/// The number of words at the bottom of the stack checked by [`canary_intact`].
+const CANARY_WORDS: usize = 4;
+
+/// Bytes below the current stack position that [`init`] leaves untouched, to avoid overwriting its
+/// own stack frame.
+const MARGIN: usize = 64;
+
+#[inline(always)]
+fn low() -> usize {
+    addr_of!(__stack_low) as usize
+}
+
+#[inline(always)]
+fn high() -> usize {
+    addr_of!(__stack_high) as usize
+}
+
+/// The total size of the stack in bytes.
+#[inline]
+pub fn stack_size() -> usize {
+    high() - low()
+}
+
+/// Fill the unused part of the stack with a known pattern. This should be called once, at the very
+/// start of the program, before any of the other functions in this module are used.
+///
+/// This writes to every unused word of the stack, which is quite slow with a large stack.
+#[inline(never)]
+pub fn init() {
+    let marker = 0u8;
+    let top = (addr_of!(marker) as usize).saturating_sub(MARGIN) & !3;
+    let mut word = (low() + 3) & !3;
+    while word < top {
+        unsafe { (word as *mut u32).write_volatile(PATTERN) };
+        word += 4;
+    }
+}
+
+/// The largest number of bytes of stack that have been in use at once since [`init`] was called.
+///
+/// This works by finding the lowest word of the stack that no longer contains the pattern written by
+/// [`init`], so it may underestimate usage if the program wrote the pattern value itself.
+pub fn stack_high_water() -> usize {
+    let mut word = (low() + 3) & !3;
+    while word < high() && unsafe { (word as *const u32).read_volatile() } == PATTERN {
+        word += 4;
+    }
+    high() - word
+}
+
+/// Whether the words at the very bottom of the stack still contain the pattern written by [`init`].
+/// If not, the stack has been (or has nearly been) overflowed.
+pub fn canary_intact() -> bool {
+    let bottom = (low() + 3) & !3;
+    (0..CANARY_WORDS)
+        .all(|i| unsafe { ((bottom + i * 4) as *const u32).read_volatile() } == PATTERN)
+}
+
+/// Panic if the stack has overflowed (or come within a few bytes of overflowing) since
+/// [`stack::init`](crate::stack::init) was called.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{check_stack, stack};
+/// stack::init();
+/// loop {
+///     // ...
+///     check_stack!();
+/// }
+/// ```
+#[macro_export]
+macro_rules! check_stack {
+    () => {
+        if !$crate::stack::canary_intact() {
+            ::core::panic!("stack overflow detected");
+        }
+    };
+}
@@ -0,0 +1,264 @@
+//! Coloured and styled chat text.
+//!
+//! Minecraft styles chat text with formatting codes: a `§` followed by a character choosing a colour
+//! or style. wasmcraft2 only prints printable ASCII characters, so with a standard build of wasmcraft2,
+//! `§` is printed as a `�` symbol and formatting codes cannot be used. [`Styled`] and [`Style::print`]
+//! therefore print plain text by default, and only emit formatting codes once they have been turned on
+//! with [`set_enabled`], for builds of wasmcraft2 that pass `§` through to the chat. Code that styles
+//! its output works either way, so it does not need to know which wasmcraft2 it is running on.
+//!
+//! While enabled, log messages from the [`logging`](crate::logging) macros have their level coloured
+//! (see [`Level::color`](crate::logging::Level::color)).
+//!
+//! Usage:
+//! ```ignore
+//! # use mcinterface::{println, style::{Color, Style, Styled}};
+//! mcinterface::style::set_enabled(true);
+//! println!("{} {}", Styled::new("FAILED").color(Color::Red).bold(), "3 tests");
+//! Style::new().color(Color::Green).print("ok");
+//! ```
+
+use crate::global::global;
+use crate::{mc_putc, print_str};
+use core::fmt::{Display, Formatter, Write};
+
+global! {
+    /// Whether formatting codes are printed.
+    static ENABLED: bool = false;
+}
+
+/// Set whether formatting codes are printed. They are not printed by default, since standard builds
+/// of wasmcraft2 cannot print `§`.
+#[inline]
+pub fn set_enabled(enabled: bool) {
+    ENABLED.set(enabled)
+}
+
+/// Whether formatting codes are printed (see [`set_enabled`]).
+#[inline]
+pub fn enabled() -> bool {
+    ENABLED.get()
+}
+
+/// A chat text colour.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub enum Color {
+    Black,
+    DarkBlue,
+    DarkGreen,
+    DarkAqua,
+    DarkRed,
+    DarkPurple,
+    Gold,
+    Gray,
+    DarkGray,
+    Blue,
+    Green,
+    Aqua,
+    Red,
+    LightPurple,
+    Yellow,
+    White,
+}
+
+impl Color {
+    /// The character used for this colour in formatting codes.
+    pub const fn code(self) -> char {
+        match self {
+            Color::Black => '0',
+            Color::DarkBlue => '1',
+            Color::DarkGreen => '2',
+            Color::DarkAqua => '3',
+            Color::DarkRed => '4',
+            Color::DarkPurple => '5',
+            Color::Gold => '6',
+            Color::Gray => '7',
+            Color::DarkGray => '8',
+            Color::Blue => '9',
+            Color::Green => 'a',
+            Color::Aqua => 'b',
+            Color::Red => 'c',
+            Color::LightPurple => 'd',
+            Color::Yellow => 'e',
+            Color::White => 'f',
+        }
+    }
+}
+
+/// A combination of a colour and text styles.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Default, Hash)]
+pub struct Style {
+    color: Option<Color>,
+    bold: bool,
+    italic: bool,
+    underlined: bool,
+    strikethrough: bool,
+    obfuscated: bool,
+}
+
+impl Style {
+    /// A style with no colour or styles.
+    pub const fn new() -> Self {
+        Self {
+            color: None,
+            bold: false,
+            italic: false,
+            underlined: false,
+            strikethrough: false,
+            obfuscated: false,
+        }
+    }
+
+    /// Set the colour.
+    pub const fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Make the text bold.
+    pub const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Make the text italic.
+    pub const fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    /// Underline the text.
+    pub const fn underlined(mut self) -> Self {
+        self.underlined = true;
+        self
+    }
+
+    /// Strike through the text.
+    pub const fn strikethrough(mut self) -> Self {
+        self.strikethrough = true;
+        self
+    }
+
+    /// Make the text constantly change to random characters.
+    pub const fn obfuscated(mut self) -> Self {
+        self.obfuscated = true;
+        self
+    }
+
+    /// Call `f` with each formatting code character for this style. The colour comes first, since
+    /// colour codes reset the other styles.
+    fn codes(&self, mut f: impl FnMut(char)) {
+        if let Some(color) = self.color {
+            f(color.code());
+        }
+        let styles = [
+            (self.obfuscated, 'k'),
+            (self.bold, 'l'),
+            (self.strikethrough, 'm'),
+            (self.underlined, 'n'),
+            (self.italic, 'o'),
+        ];
+        for (set, code) in styles {
+            if set {
+                f(code);
+            }
+        }
+    }
+
+    /// Print a string in this style, followed by a reset code, without using `core::fmt`. Prints just
+    /// the string if formatting codes are not enabled (see [`set_enabled`]).
+    pub fn print(&self, s: &str) {
+        if !enabled() {
+            print_str(s);
+            return;
+        }
+        self.codes(|code| {
+            mc_putc('§');
+            mc_putc(code);
+        });
+        print_str(s);
+        print_str("§r");
+    }
+
+    /// Apply this style to a value, for printing with [`print!`](crate::print) and similar macros.
+    #[inline]
+    pub const fn apply<T>(self, value: T) -> Styled<T> {
+        Styled { value, style: self }
+    }
+}
+
+/// A value that is displayed in a [`Style`], followed by a reset code.
+///
+/// If formatting codes are not enabled (see [`set_enabled`]), it is displayed exactly like the value.
+#[derive(Copy, Clone, Debug)]
+pub struct Styled<T> {
+    value: T,
+    style: Style,
+}
+
+impl<T> Styled<T> {
+    /// Wrap a value with no colour or styles.
+    #[inline]
+    pub const fn new(value: T) -> Self {
+        Style::new().apply(value)
+    }
+
+    /// Set the colour.
+    #[inline]
+    pub const fn color(mut self, color: Color) -> Self {
+        self.style = self.style.color(color);
+        self
+    }
+
+    /// Make the text bold.
+    #[inline]
+    pub const fn bold(mut self) -> Self {
+        self.style = self.style.bold();
+        self
+    }
+
+    /// Make the text italic.
+    #[inline]
+    pub const fn italic(mut self) -> Self {
+        self.style = self.style.italic();
+        self
+    }
+
+    /// Underline the text.
+    #[inline]
+    pub const fn underlined(mut self) -> Self {
+        self.style = self.style.underlined();
+        self
+    }
+
+    /// Strike through the text.
+    #[inline]
+    pub const fn strikethrough(mut self) -> Self {
+        self.style = self.style.strikethrough();
+        self
+    }
+
+    /// Make the text constantly change to random characters.
+    #[inline]
+    pub const fn obfuscated(mut self) -> Self {
+        self.style = self.style.obfuscated();
+        self
+    }
+}
+
+impl<T: Display> Display for Styled<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if !enabled() {
+            return self.value.fmt(f);
+        }
+        let mut result = Ok(());
+        self.style.codes(|code| {
+            if result.is_ok() {
+                result = f.write_char('§').and_then(|_| f.write_char(code));
+            }
+        });
+        result?;
+        self.value.fmt(f)?;
+        f.write_str("§r")
+    }
+}
@@ -0,0 +1,206 @@
+//! Writing multi-tick logic as async functions.
+//!
+//! Programs that do a lot of work need to spread it over many game ticks, which usually means either
+//! calling [`mc_sleep`] in the middle of the work or writing a state machine that does a little of it
+//! each tick. This module allows the work to be written as ordinary `async` functions instead:
+//! awaiting [`next_tick`] pauses until the next game tick, and [`join`] (or [`join!`](crate::join))
+//! runs several futures "concurrently", advancing each of them every tick. [`block_on`] runs a future
+//! to completion, calling [`mc_sleep`] whenever every task is waiting for the next tick.
+//!
+//! Usage:
+//! ```ignore
+//! # use mcinterface::{join, task::{block_on, next_tick}, turtle_pos, turtle_set, Block};
+//! async fn build_tower(x: i32, height: i32) {
+//!     for y in 0..height {
+//!         turtle_pos(x, 64 + y, 0);
+//!         turtle_set(Block::Cobblestone);
+//!         next_tick().await;
+//!     }
+//! }
+//!
+//! block_on(async {
+//!     join!(build_tower(0, 10), build_tower(4, 20), build_tower(8, 5)).await;
+//! });
+//! ```
+//!
+//! The executor does not use wakers: every pending future is polled again after each tick. Futures
+//! from other crates that wait to be woken will still work, but are polled once per tick while they
+//! wait.
+
+use crate::{mc_sleep, ticks};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+/// A waker that does nothing, since [`block_on`] polls every tick anyway.
+fn noop_waker() -> Waker {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |_| RawWaker::new(core::ptr::null(), &VTABLE),
+        |_| {},
+        |_| {},
+        |_| {},
+    );
+    unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) }
+}
+
+/// Run a future to completion, calling [`mc_sleep`] each time it is waiting for the next tick.
+///
+/// This should normally only be called once, from the entrypoint; calling it from inside a future it
+/// is running would make the outer future stop advancing until the inner one completes.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        mc_sleep();
+    }
+}
+
+/// A future that completes once a given number of ticks have passed, created by [`next_tick`] or
+/// [`wait_ticks`].
+#[must_use = "futures do nothing unless awaited"]
+pub struct WaitTicks {
+    ticks: u32,
+    /// The tick at which the future completes, set when it is first polled.
+    until: Option<u32>,
+}
+
+impl Future for WaitTicks {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        let now = ticks();
+        let wait = self.ticks;
+        let until = *self.until.get_or_insert(now.wrapping_add(wait));
+        if (now.wrapping_sub(until) as i32) >= 0 {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Pause until the next game tick.
+///
+/// This must be awaited inside a future run by [`block_on`]; other executors will never complete it.
+#[inline(always)]
+pub fn next_tick() -> WaitTicks {
+    wait_ticks(1)
+}
+
+/// Pause for the given number of game ticks. Waiting for 0 ticks completes immediately.
+///
+/// This must be awaited inside a future run by [`block_on`]; other executors will never complete it.
+#[inline(always)]
+pub fn wait_ticks(ticks: u32) -> WaitTicks {
+    WaitTicks { ticks, until: None }
+}
+
+/// A future that may have completed, used by [`Join`].
+enum MaybeDone<F: Future> {
+    Pending(F),
+    Done(F::Output),
+    Taken,
+}
+
+impl<F: Future> MaybeDone<F> {
+    /// Poll the future if it has not completed yet, and return whether it has completed.
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> bool {
+        // SAFETY: the inner future is never moved; it is dropped in place when it completes
+        let this = unsafe { self.get_unchecked_mut() };
+        if let MaybeDone::Pending(future) = this {
+            if let Poll::Ready(output) = unsafe { Pin::new_unchecked(future) }.poll(cx) {
+                *this = MaybeDone::Done(output);
+            }
+        }
+        !matches!(this, MaybeDone::Pending(_))
+    }
+
+    /// Take the output of the completed future.
+    fn take(self: Pin<&mut Self>) -> F::Output {
+        // SAFETY: only called once the future has completed, so there is nothing pinned to move
+        let this = unsafe { self.get_unchecked_mut() };
+        match core::mem::replace(this, MaybeDone::Taken) {
+            MaybeDone::Done(output) => output,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// A future that runs two futures concurrently, created by [`join`].
+#[must_use = "futures do nothing unless awaited"]
+pub struct Join<A: Future, B: Future> {
+    a: MaybeDone<A>,
+    b: MaybeDone<B>,
+}
+
+impl<A: Future, B: Future> Future for Join<A, B> {
+    type Output = (A::Output, B::Output);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: the fields are never moved out of the pinned struct
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut a = unsafe { Pin::new_unchecked(&mut this.a) };
+        let mut b = unsafe { Pin::new_unchecked(&mut this.b) };
+        let a_done = a.as_mut().poll(cx);
+        let b_done = b.as_mut().poll(cx);
+        if a_done && b_done {
+            Poll::Ready((a.take(), b.take()))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Run two futures concurrently, completing with both of their outputs once both have completed.
+///
+/// Each time the returned future is polled, both futures are polled (until they complete), so they
+/// both advance every tick. To join more than two futures, use [`join!`](crate::join).
+pub fn join<A: Future, B: Future>(a: A, b: B) -> Join<A, B> {
+    Join { a: MaybeDone::Pending(a), b: MaybeDone::Pending(b) }
+}
+
+/// Run several futures concurrently, completing with a tuple of their outputs once all of them have
+/// completed. Up to 6 futures can be joined.
+///
+/// See [`task::join`](crate::task::join).
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::join;
+/// let (a, b, c) = join!(task_a(), task_b(), task_c()).await;
+/// ```
+#[macro_export]
+macro_rules! join {
+    ($a:expr, $b:expr $(,)?) => {
+        $crate::task::join($a, $b)
+    };
+    ($a:expr, $b:expr, $c:expr $(,)?) => {
+        async {
+            let (a, (b, c)) = $crate::task::join($a, $crate::join!($b, $c)).await;
+            (a, b, c)
+        }
+    };
+    ($a:expr, $b:expr, $c:expr, $d:expr $(,)?) => {
+        async {
+            let (a, (b, c, d)) = $crate::task::join($a, $crate::join!($b, $c, $d)).await;
+            (a, b, c, d)
+        }
+    };
+    ($a:expr, $b:expr, $c:expr, $d:expr, $e:expr $(,)?) => {
+        async {
+            let (a, (b, c, d, e)) = $crate::task::join($a, $crate::join!($b, $c, $d, $e)).await;
+            (a, b, c, d, e)
+        }
+    };
+    ($a:expr, $b:expr, $c:expr, $d:expr, $e:expr, $f:expr $(,)?) => {
+        async {
+            let (a, (b, c, d, e, f)) =
+                $crate::task::join($a, $crate::join!($b, $c, $d, $e, $f)).await;
+            (a, b, c, d, e, f)
+        }
+    };
+}
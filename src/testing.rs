@@ -0,0 +1,171 @@
+//! A small test framework for running tests inside Minecraft.
+//!
+//! Mark test functions with the [`mc_test`](crate::mc_test) attribute, then list them in
+//! [`mc_test_main!`](crate::mc_test_main) to generate an entrypoint that runs them one after another,
+//! printing `[PASS]` or `[FAIL]` and the test name to the game chat for each test, and a summary at
+//! the end:
+//! ```ignore
+//! # use mcinterface::{mc_test, mc_test_main, mc_assert_eq, turtle_pos, turtle_get, Block};
+//! #[mc_test]
+//! fn places_a_block() {
+//!     turtle_pos(0, 64, 0);
+//!     my_library::place_marker();
+//!     mc_assert_eq!(turtle_get(), Block::Gold);
+//! }
+//!
+//! #[mc_test(should_panic)]
+//! fn rejects_bad_input() {
+//!     my_library::parse("nonsense");
+//! }
+//!
+//! mc_test_main!(places_a_block, rejects_bad_input);
+//! ```
+//!
+//! WebAssembly has no linker sections that can be collected at runtime, so tests have to be listed
+//! explicitly. So that a test cannot be forgotten, a test that is marked with `#[mc_test]` but not
+//! listed is a compile error (``constant `name` is never used``). Tests are visible to the whole
+//! crate whatever visibility the function is declared with, so they can be listed by path from
+//! anywhere in the crate, but they cannot be listed from another crate.
+//!
+//! wasmcraft2 cannot unwind the stack, so when a test panics, the panic handler records the failure
+//! and carries on running the remaining tests from inside the panic handler. The stack used by the
+//! failed test is never reclaimed, and the entrypoint never returns once a test has panicked. With
+//! the `simulator` feature enabled, panics are caught normally instead.
+
+use crate::global::global;
+
+/// A test marked with [`mc_test`](crate::mc_test).
+#[derive(Copy, Clone, Debug)]
+pub struct TestCase {
+    /// The full path of the test function.
+    pub name: &'static str,
+    /// The test function.
+    pub run: fn(),
+    /// Whether the test is expected to panic.
+    pub should_panic: bool,
+}
+
+/// The progress of the tests being run by [`run`].
+struct State {
+    tests: &'static [TestCase],
+    next: usize,
+    passed: u32,
+    failed: u32,
+}
+
+global! {
+    /// The tests being run, if [`run`] is running.
+    static STATE: Option<State> = None;
+}
+
+/// Run each of the given tests in order, printing the result of each test and a summary to the game
+/// chat. Returns the number of tests that failed.
+///
+/// This is normally called by the entrypoint generated by [`mc_test_main!`](crate::mc_test_main).
+pub fn run(tests: &'static [TestCase]) -> u32 {
+    STATE.set(Some(State {
+        tests,
+        next: 0,
+        passed: 0,
+        failed: 0,
+    }));
+    run_remaining()
+}
+
+/// Take the next test to run, if there is one.
+fn next_test() -> Option<TestCase> {
+    STATE.with(|state| {
+        let state = state.as_mut()?;
+        let test = *state.tests.get(state.next)?;
+        state.next += 1;
+        Some(test)
+    })
+}
+
+/// Record and print the result of a test.
+fn finish(test: &TestCase, panicked: bool) {
+    let passed = panicked == test.should_panic;
+    let running = STATE.with(|state| {
+        let Some(state) = state else {
+            return false;
+        };
+        if passed {
+            state.passed += 1;
+        } else {
+            state.failed += 1;
+        }
+        true
+    });
+    if !running {
+        return;
+    }
+    crate::print_str(if passed { "[PASS] " } else { "[FAIL] " });
+    crate::print_str(test.name);
+    if test.should_panic && !panicked {
+        crate::print_str(" (did not panic)");
+    }
+    crate::mc_putc('\n');
+}
+
+/// Run every test that has not been run yet, then print the summary and return the number of
+/// failures.
+fn run_remaining() -> u32 {
+    while let Some(test) = next_test() {
+        #[cfg(feature = "simulator")]
+        let panicked = std::panic::catch_unwind(test.run).is_err();
+        #[cfg(not(feature = "simulator"))]
+        let panicked = {
+            (test.run)();
+            false
+        };
+        finish(&test, panicked);
+    }
+    let Some(state) = STATE.replace(None) else {
+        return 0;
+    };
+    crate::fmt::print_u32(state.passed);
+    crate::print_str(" passed, ");
+    crate::fmt::print_u32(state.failed);
+    crate::println(" failed");
+    state.failed
+}
+
+/// Called by the panic handler. If a test is running, this fails it and runs the remaining tests
+/// without returning; otherwise it returns immediately.
+#[cfg(not(any(test, feature = "simulator")))]
+pub(crate) fn on_panic(info: &core::panic::PanicInfo) {
+    let current = STATE.with(|state| match state {
+        Some(state) if state.next > 0 => Some(state.tests[state.next - 1]),
+        _ => None,
+    });
+    let Some(current) = current else {
+        return;
+    };
+    finish(&current, true);
+    if !current.should_panic {
+        crate::panic::report_message(info);
+    }
+    run_remaining();
+    loop {
+        crate::mc_sleep();
+    }
+}
+
+/// Generate an entrypoint that runs the given tests with [`testing::run`](crate::testing::run) and
+/// returns the number of failures. Each test must be marked with [`mc_test`](crate::mc_test).
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::mc_test_main;
+/// mc_test_main!(places_a_block, shapes::tests::draws_a_circle);
+/// ```
+#[macro_export]
+macro_rules! mc_test_main {
+    ($($test:path),* $(,)?) => {
+        #[no_mangle]
+        pub extern "C" fn _start() -> i32 {
+            static TESTS: &[$crate::testing::TestCase] = &[$($test),*];
+            $crate::testing::run(TESTS) as i32
+        }
+    };
+}
@@ -0,0 +1,188 @@
+//! Text rendering using blocks.
+//!
+//! Contains a built-in font covering the printable ASCII characters, and functions for drawing text
+//! into the world (with [`draw_text`]) or onto a [`Screen`](crate::screen::Screen) (with
+//! [`Screen::draw_text`](crate::screen::Screen::draw_text)).
+//!
+//! The font is not a full 5x7 font. Each glyph takes up a cell [`GLYPH_WIDTH`] (5) blocks wide and
+//! [`GLYPH_HEIGHT`] (7) blocks tall, but only the left 4 columns and the top 6 rows are used for the
+//! character itself: the fifth column is always empty, as spacing between characters, and the
+//! bottom row is only used by descenders (such as `g` and `,`). This means characters can be placed
+//! directly next to each other, and lines [`LINE_HEIGHT`] blocks apart, without touching.
+
+use crate::{turtle_pos, turtle_position, turtle_set, turtle_x, turtle_y, Block};
+
+/// The width of a glyph in blocks: 4 columns of pixels and 1 column of spacing.
+pub const GLYPH_WIDTH: usize = 5;
+
+/// The height of a glyph in blocks: 6 rows of pixels and 1 row for descenders.
+pub const GLYPH_HEIGHT: usize = 7;
+
+/// The vertical distance between the top of one line of text and the top of the next line.
+pub const LINE_HEIGHT: usize = GLYPH_HEIGHT + 1;
+
+/// The font data for the printable ASCII characters, starting from `' '`.
+///
+/// Each glyph is stored as 7 rows from top to bottom, with the most significant of the 5 used bits
+/// of each row being the leftmost pixel. The least significant bit is the spacing column, and is
+/// never set.
+static FONT: [[u8; GLYPH_HEIGHT]; 95] = [
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000], // ' '
+    [0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100, 0b00000], // '!'
+    [0b01010, 0b01010, 0b01010, 0b00000, 0b00000, 0b00000, 0b00000], // '"'
+    [0b00000, 0b01010, 0b11110, 0b01010, 0b11110, 0b01010, 0b00000], // '#'
+    [0b00100, 0b01110, 0b11000, 0b01100, 0b00110, 0b11100, 0b00100], // '$'
+    [0b10000, 0b10010, 0b00100, 0b01000, 0b10010, 0b00010, 0b00000], // '%'
+    [0b00000, 0b01000, 0b10100, 0b01000, 0b10100, 0b01010, 0b00000], // '&'
+    [0b00100, 0b00100, 0b00100, 0b00000, 0b00000, 0b00000, 0b00000], // '\''
+    [0b00100, 0b01000, 0b01000, 0b01000, 0b01000, 0b00100, 0b00000], // '('
+    [0b01000, 0b00100, 0b00100, 0b00100, 0b00100, 0b01000, 0b00000], // ')'
+    [0b00000, 0b01010, 0b00100, 0b01110, 0b00100, 0b01010, 0b00000], // '*'
+    [0b00000, 0b01000, 0b01000, 0b11100, 0b01000, 0b01000, 0b00000], // '+'
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b00110, 0b00100, 0b01000], // ','
+    [0b00000, 0b00000, 0b00000, 0b11110, 0b00000, 0b00000, 0b00000], // '-'
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100, 0b00000], // '.'
+    [0b00000, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000, 0b00000], // '/'
+    [0b00100, 0b01010, 0b01010, 0b01010, 0b01010, 0b00100, 0b00000], // '0'
+    [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b01110, 0b00000], // '1'
+    [0b01100, 0b10010, 0b00010, 0b00100, 0b01000, 0b11110, 0b00000], // '2'
+    [0b11110, 0b00010, 0b01100, 0b00010, 0b10010, 0b01100, 0b00000], // '3'
+    [0b00100, 0b01100, 0b10100, 0b11110, 0b00100, 0b00100, 0b00000], // '4'
+    [0b11110, 0b10000, 0b11100, 0b00010, 0b10010, 0b01100, 0b00000], // '5'
+    [0b01100, 0b10000, 0b11100, 0b10010, 0b10010, 0b01100, 0b00000], // '6'
+    [0b11110, 0b00010, 0b00100, 0b00100, 0b01000, 0b01000, 0b00000], // '7'
+    [0b01100, 0b10010, 0b01100, 0b10010, 0b10010, 0b01100, 0b00000], // '8'
+    [0b01100, 0b10010, 0b10010, 0b01110, 0b00010, 0b01100, 0b00000], // '9'
+    [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000], // ':'
+    [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01000, 0b10000], // ';'
+    [0b00000, 0b00010, 0b00100, 0b01000, 0b00100, 0b00010, 0b00000], // '<'
+    [0b00000, 0b00000, 0b11110, 0b00000, 0b11110, 0b00000, 0b00000], // '='
+    [0b00000, 0b01000, 0b00100, 0b00010, 0b00100, 0b01000, 0b00000], // '>'
+    [0b00100, 0b01010, 0b00010, 0b00100, 0b00000, 0b00100, 0b00000], // '?'
+    [0b01100, 0b10010, 0b10110, 0b10110, 0b10000, 0b01100, 0b00000], // '@'
+    [0b01100, 0b10010, 0b10010, 0b11110, 0b10010, 0b10010, 0b00000], // 'A'
+    [0b11100, 0b10010, 0b11100, 0b10010, 0b10010, 0b11100, 0b00000], // 'B'
+    [0b01100, 0b10010, 0b10000, 0b10000, 0b10010, 0b01100, 0b00000], // 'C'
+    [0b11100, 0b10010, 0b10010, 0b10010, 0b10010, 0b11100, 0b00000], // 'D'
+    [0b11110, 0b10000, 0b11100, 0b10000, 0b10000, 0b11110, 0b00000], // 'E'
+    [0b11110, 0b10000, 0b11100, 0b10000, 0b10000, 0b10000, 0b00000], // 'F'
+    [0b01100, 0b10010, 0b10000, 0b10110, 0b10010, 0b01110, 0b00000], // 'G'
+    [0b10010, 0b10010, 0b11110, 0b10010, 0b10010, 0b10010, 0b00000], // 'H'
+    [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110, 0b00000], // 'I'
+    [0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100, 0b00000], // 'J'
+    [0b10010, 0b10100, 0b11000, 0b11000, 0b10100, 0b10010, 0b00000], // 'K'
+    [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11110, 0b00000], // 'L'
+    [0b10010, 0b11110, 0b11110, 0b10010, 0b10010, 0b10010, 0b00000], // 'M'
+    [0b10010, 0b11010, 0b11010, 0b10110, 0b10110, 0b10010, 0b00000], // 'N'
+    [0b01100, 0b10010, 0b10010, 0b10010, 0b10010, 0b01100, 0b00000], // 'O'
+    [0b11100, 0b10010, 0b10010, 0b11100, 0b10000, 0b10000, 0b00000], // 'P'
+    [0b01100, 0b10010, 0b10010, 0b10010, 0b11010, 0b01100, 0b00010], // 'Q'
+    [0b11100, 0b10010, 0b10010, 0b11100, 0b10100, 0b10010, 0b00000], // 'R'
+    [0b01100, 0b10010, 0b01000, 0b00100, 0b10010, 0b01100, 0b00000], // 'S'
+    [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000], // 'T'
+    [0b10010, 0b10010, 0b10010, 0b10010, 0b10010, 0b01100, 0b00000], // 'U'
+    [0b10010, 0b10010, 0b10010, 0b10010, 0b01100, 0b01100, 0b00000], // 'V'
+    [0b10010, 0b10010, 0b10010, 0b11110, 0b11110, 0b10010, 0b00000], // 'W'
+    [0b10010, 0b10010, 0b01100, 0b01100, 0b10010, 0b10010, 0b00000], // 'X'
+    [0b01010, 0b01010, 0b01010, 0b00100, 0b00100, 0b00100, 0b00000], // 'Y'
+    [0b11110, 0b00010, 0b00100, 0b01000, 0b10000, 0b11110, 0b00000], // 'Z'
+    [0b01110, 0b01000, 0b01000, 0b01000, 0b01000, 0b01110, 0b00000], // '['
+    [0b00000, 0b10000, 0b01000, 0b00100, 0b00010, 0b00000, 0b00000], // '\\'
+    [0b01110, 0b00010, 0b00010, 0b00010, 0b00010, 0b01110, 0b00000], // ']'
+    [0b00100, 0b01010, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000], // '^'
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b11110, 0b00000], // '_'
+    [0b01000, 0b00100, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000], // '`'
+    [0b00000, 0b00000, 0b01110, 0b10010, 0b10110, 0b01010, 0b00000], // 'a'
+    [0b10000, 0b10000, 0b11100, 0b10010, 0b10010, 0b11100, 0b00000], // 'b'
+    [0b00000, 0b00000, 0b01100, 0b10000, 0b10000, 0b01100, 0b00000], // 'c'
+    [0b00010, 0b00010, 0b01110, 0b10010, 0b10010, 0b01110, 0b00000], // 'd'
+    [0b00000, 0b00000, 0b01100, 0b10110, 0b11000, 0b01100, 0b00000], // 'e'
+    [0b00100, 0b01010, 0b01000, 0b11100, 0b01000, 0b01000, 0b00000], // 'f'
+    [0b00000, 0b00000, 0b01110, 0b10010, 0b01100, 0b10000, 0b01110], // 'g'
+    [0b10000, 0b10000, 0b11100, 0b10010, 0b10010, 0b10010, 0b00000], // 'h'
+    [0b00100, 0b00000, 0b01100, 0b00100, 0b00100, 0b01110, 0b00000], // 'i'
+    [0b00010, 0b00000, 0b00010, 0b00010, 0b00010, 0b01010, 0b00100], // 'j'
+    [0b10000, 0b10000, 0b10100, 0b11000, 0b10100, 0b10010, 0b00000], // 'k'
+    [0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110, 0b00000], // 'l'
+    [0b00000, 0b00000, 0b10100, 0b11110, 0b10010, 0b10010, 0b00000], // 'm'
+    [0b00000, 0b00000, 0b11100, 0b10010, 0b10010, 0b10010, 0b00000], // 'n'
+    [0b00000, 0b00000, 0b01100, 0b10010, 0b10010, 0b01100, 0b00000], // 'o'
+    [0b00000, 0b00000, 0b11100, 0b10010, 0b10010, 0b11100, 0b10000], // 'p'
+    [0b00000, 0b00000, 0b01110, 0b10010, 0b10010, 0b01110, 0b00010], // 'q'
+    [0b00000, 0b00000, 0b11100, 0b10010, 0b10000, 0b10000, 0b00000], // 'r'
+    [0b00000, 0b00000, 0b01110, 0b11000, 0b00110, 0b11100, 0b00000], // 's'
+    [0b01000, 0b01000, 0b11100, 0b01000, 0b01000, 0b00110, 0b00000], // 't'
+    [0b00000, 0b00000, 0b10010, 0b10010, 0b10010, 0b01110, 0b00000], // 'u'
+    [0b00000, 0b00000, 0b01010, 0b01010, 0b01010, 0b00100, 0b00000], // 'v'
+    [0b00000, 0b00000, 0b10010, 0b10010, 0b11110, 0b11110, 0b00000], // 'w'
+    [0b00000, 0b00000, 0b10010, 0b01100, 0b01100, 0b10010, 0b00000], // 'x'
+    [0b00000, 0b00000, 0b10010, 0b10010, 0b01010, 0b00100, 0b01000], // 'y'
+    [0b00000, 0b00000, 0b11110, 0b00100, 0b01000, 0b11110, 0b00000], // 'z'
+    [0b00010, 0b00100, 0b01100, 0b00100, 0b00100, 0b00010, 0b00000], // '{'
+    [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000], // '|'
+    [0b01000, 0b00100, 0b00110, 0b00100, 0b00100, 0b01000, 0b00000], // '}'
+    [0b01010, 0b10100, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000], // '~'
+];
+
+/// Get the glyph for a character, as 7 rows from top to bottom with the most significant of the 5
+/// used bits of each row being the leftmost pixel.
+///
+/// Characters that are not printable ASCII characters are drawn as `'?'`.
+#[inline]
+pub fn glyph(c: char) -> &'static [u8; GLYPH_HEIGHT] {
+    match c {
+        ' '..='~' => &FONT[c as usize - ' ' as usize],
+        _ => &FONT['?' as usize - ' ' as usize],
+    }
+}
+
+/// Check whether the pixel at column `x` of a glyph row is set.
+#[inline(always)]
+pub fn glyph_pixel(row: u8, x: usize) -> bool {
+    row & (1 << (GLYPH_WIDTH - 1 - x)) != 0
+}
+
+/// Get the width in blocks of the widest line of a string when drawn with [`draw_text`].
+pub fn text_width(s: &str) -> usize {
+    s.split('\n').map(|line| line.chars().count() * GLYPH_WIDTH).max().unwrap_or(0)
+}
+
+/// Get the height in blocks of a string when drawn with [`draw_text`].
+pub fn text_height(s: &str) -> usize {
+    (s.split('\n').count() - 1) * LINE_HEIGHT + GLYPH_HEIGHT
+}
+
+/// Draw text into the world on a vertical wall in the XY plane, starting at the turtle position.
+///
+/// The turtle position is the top left corner of the first character; text is drawn towards positive
+/// x and each line (separated by `'\n'`) is drawn [`LINE_HEIGHT`] blocks below the previous one.
+/// Pixels of the glyphs are drawn with `block`. If `background` is `Some`, the remaining pixels of
+/// each glyph are filled with that block, otherwise they are left unchanged.
+///
+/// The turtle is returned to its starting position afterwards.
+///
+/// Usage:
+/// ```ignore
+/// # use mcinterface::{Block, turtle_pos, text::draw_text};
+/// turtle_pos(0, 100, 0);
+/// draw_text("Hello, World!", Block::Gold, Some(Block::Air));
+/// ```
+pub fn draw_text(s: &str, block: Block, background: Option<Block>) {
+    let (x, y, z) = turtle_position();
+    for (line_index, line) in s.split('\n').enumerate() {
+        for row in 0..GLYPH_HEIGHT {
+            turtle_y(y - (line_index * LINE_HEIGHT + row) as i32);
+            for (char_index, c) in line.chars().enumerate() {
+                let bits = glyph(c)[row];
+                for column in 0..GLYPH_WIDTH {
+                    let pixel = if glyph_pixel(bits, column) { Some(block) } else { background };
+                    if let Some(pixel) = pixel {
+                        turtle_x(x + (char_index * GLYPH_WIDTH + column) as i32);
+                        turtle_set(pixel);
+                    }
+                }
+            }
+        }
+    }
+    turtle_pos(x, y, z);
+}
@@ -0,0 +1,106 @@
+//! Rotating and mirroring block data in memory.
+//!
+//! wasmcraft2's region copy and paste functions cannot transform regions, so to place a rotated or
+//! mirrored copy of a structure, read it into a buffer with [`scan_region`](crate::scan::scan_region),
+//! transform the buffer with [`rotate_y`] or [`mirror`], then place it again with
+//! [`place_region`](crate::scan::place_region).
+//!
+//! All functions in this module work on buffers in [`ScanOrder::RowMajor`](crate::scan::ScanOrder)
+//! order.
+//!
+//! Usage:
+//! ```ignore
+//! # use mcinterface::{Block, region::Region, scan::{place_region, scan_region, ScanOrder}};
+//! # use mcinterface::transform::{rotate_y, Rotation};
+//! let region = Region::from_size((0, 64, 0), (4, 3, 2));
+//! let (mut src, mut dst) = ([Block::Air; 24], [Block::Air; 24]);
+//! scan_region(&region, &mut src, ScanOrder::RowMajor);
+//! let rotated = rotate_y(&region, &src, &mut dst, Rotation::Clockwise90).unwrap();
+//! let target = Region::new((10, 64, 0), rotated.span);
+//! place_region(&target, &dst, ScanOrder::RowMajor);
+//! ```
+
+use crate::region::Region;
+pub use crate::vec::Axis;
+use crate::Block;
+
+/// A rotation about the y axis, as seen from above.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Rotation {
+    /// Rotate by 90 degrees clockwise, so that blocks facing east (positive x) end up facing south
+    /// (positive z).
+    Clockwise90,
+    /// Rotate by 180 degrees.
+    Rotate180,
+    /// Rotate by 90 degrees anticlockwise (270 degrees clockwise).
+    Anticlockwise90,
+}
+
+/// Rotate the block data of a region about the y axis, writing the result to `dst`.
+///
+/// Returns a region with the same origin as `region` and the size of the rotated data (for 90 degree
+/// rotations, the x and z sizes are swapped), or `None` if either buffer is smaller than the region.
+pub fn rotate_y(
+    region: &Region,
+    src: &[Block],
+    dst: &mut [Block],
+    rotation: Rotation,
+) -> Option<Region> {
+    let volume = region.volume();
+    if src.len() < volume || dst.len() < volume {
+        return None;
+    }
+    let (size_x, size_y, size_z) = region.size();
+    let (size_x, size_y, size_z) = (size_x as usize, size_y as usize, size_z as usize);
+    let (new_size_x, new_size_z) = match rotation {
+        Rotation::Rotate180 => (size_x, size_z),
+        Rotation::Clockwise90 | Rotation::Anticlockwise90 => (size_z, size_x),
+    };
+
+    for y in 0..size_y {
+        for z in 0..size_z {
+            for x in 0..size_x {
+                let (new_x, new_z) = match rotation {
+                    Rotation::Clockwise90 => (size_z - 1 - z, x),
+                    Rotation::Rotate180 => (size_x - 1 - x, size_z - 1 - z),
+                    Rotation::Anticlockwise90 => (z, size_x - 1 - x),
+                };
+                dst[(y * new_size_z + new_z) * new_size_x + new_x] =
+                    src[(y * size_z + z) * size_x + x];
+            }
+        }
+    }
+
+    Some(Region::from_size(
+        region.origin,
+        (new_size_x as i32, size_y as i32, new_size_z as i32),
+    ))
+}
+
+/// Mirror the block data of a region in place along the given axis, so that the blocks at the
+/// minimum and maximum of that axis are swapped.
+///
+/// Does nothing if the buffer is smaller than the region.
+pub fn mirror(region: &Region, buf: &mut [Block], axis: Axis) {
+    if buf.len() < region.volume() {
+        return;
+    }
+    let (size_x, size_y, size_z) = region.size();
+    let (size_x, size_y, size_z) = (size_x as usize, size_y as usize, size_z as usize);
+    let index = |x: usize, y: usize, z: usize| (y * size_z + z) * size_x + x;
+
+    for y in 0..size_y {
+        for z in 0..size_z {
+            for x in 0..size_x {
+                let (other, swap) = match axis {
+                    Axis::X => (index(size_x - 1 - x, y, z), x < size_x / 2),
+                    Axis::Y => (index(x, size_y - 1 - y, z), y < size_y / 2),
+                    Axis::Z => (index(x, y, size_z - 1 - z), z < size_z / 2),
+                };
+                if swap {
+                    buf.swap(index(x, y, z), other);
+                }
+            }
+        }
+    }
+}
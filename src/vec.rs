@@ -0,0 +1,264 @@
+//! Integer 3D vectors.
+//!
+//! Contains [`IVec3`], a block position or offset, and [`Axis`]. Functions that take positions
+//! generally accept anything that converts into an [`IVec3`], including `(x, y, z)` tuples, so
+//! positions can be kept as vectors without converting them at every call.
+//!
+//! Usage:
+//! ```ignore
+//! # use mcinterface::{turtle_pos_vec, turtle_set, vec::IVec3, Block};
+//! let start = IVec3::new(0, 64, 0);
+//! for step in 0..10 {
+//!     turtle_pos_vec(start + IVec3::X * step);
+//!     turtle_set(Block::Cobblestone);
+//! }
+//! ```
+
+use core::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// An axis of the world.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    /// All three axes, in order.
+    pub const ALL: [Axis; 3] = [Axis::X, Axis::Y, Axis::Z];
+}
+
+/// A 3D vector of `i32`s, used for block positions and offsets.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Default, Hash)]
+pub struct IVec3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl IVec3 {
+    /// `(0, 0, 0)`.
+    pub const ZERO: IVec3 = IVec3::new(0, 0, 0);
+    /// `(1, 0, 0)`, one block east.
+    pub const X: IVec3 = IVec3::new(1, 0, 0);
+    /// `(0, 1, 0)`, one block up.
+    pub const Y: IVec3 = IVec3::new(0, 1, 0);
+    /// `(0, 0, 1)`, one block south.
+    pub const Z: IVec3 = IVec3::new(0, 0, 1);
+
+    /// Create a vector from its components.
+    #[inline(always)]
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Create a vector with all components set to `value`.
+    #[inline(always)]
+    pub const fn splat(value: i32) -> Self {
+        Self::new(value, value, value)
+    }
+
+    /// The vector of length 1 along an axis.
+    #[inline]
+    pub const fn unit(axis: Axis) -> Self {
+        match axis {
+            Axis::X => IVec3::X,
+            Axis::Y => IVec3::Y,
+            Axis::Z => IVec3::Z,
+        }
+    }
+
+    /// The component along an axis.
+    #[inline]
+    pub const fn get(self, axis: Axis) -> i32 {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y,
+            Axis::Z => self.z,
+        }
+    }
+
+    /// A copy of the vector with the component along an axis replaced.
+    #[inline]
+    pub const fn with(self, axis: Axis, value: i32) -> Self {
+        match axis {
+            Axis::X => IVec3::new(value, self.y, self.z),
+            Axis::Y => IVec3::new(self.x, value, self.z),
+            Axis::Z => IVec3::new(self.x, self.y, value),
+        }
+    }
+
+    /// The components as an `(x, y, z)` tuple.
+    #[inline(always)]
+    pub const fn to_tuple(self) -> (i32, i32, i32) {
+        (self.x, self.y, self.z)
+    }
+
+    /// The minimum of each component.
+    #[inline]
+    pub fn min(self, other: IVec3) -> IVec3 {
+        IVec3::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+    }
+
+    /// The maximum of each component.
+    #[inline]
+    pub fn max(self, other: IVec3) -> IVec3 {
+        IVec3::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+    }
+
+    /// The absolute value of each component.
+    #[inline]
+    pub const fn abs(self) -> IVec3 {
+        IVec3::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    /// The Manhattan (taxicab) distance to another position: the number of single-block steps along
+    /// the axes needed to get there.
+    #[inline]
+    pub const fn manhattan(self, other: IVec3) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// The Chebyshev distance to another position: the largest difference along any axis, which is
+    /// the number of steps needed if diagonal steps are allowed.
+    #[inline]
+    pub fn chebyshev(self, other: IVec3) -> u32 {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    /// The 6 positions that share a face with this one, in the order -x, +x, -y, +y, -z, +z.
+    #[inline]
+    pub fn neighbours(self) -> [IVec3; 6] {
+        [
+            self - IVec3::X,
+            self + IVec3::X,
+            self - IVec3::Y,
+            self + IVec3::Y,
+            self - IVec3::Z,
+            self + IVec3::Z,
+        ]
+    }
+}
+
+impl From<(i32, i32, i32)> for IVec3 {
+    #[inline(always)]
+    fn from((x, y, z): (i32, i32, i32)) -> Self {
+        IVec3::new(x, y, z)
+    }
+}
+
+impl From<IVec3> for (i32, i32, i32) {
+    #[inline(always)]
+    fn from(vec: IVec3) -> Self {
+        vec.to_tuple()
+    }
+}
+
+impl From<[i32; 3]> for IVec3 {
+    #[inline(always)]
+    fn from([x, y, z]: [i32; 3]) -> Self {
+        IVec3::new(x, y, z)
+    }
+}
+
+impl From<IVec3> for [i32; 3] {
+    #[inline(always)]
+    fn from(vec: IVec3) -> Self {
+        [vec.x, vec.y, vec.z]
+    }
+}
+
+impl Index<Axis> for IVec3 {
+    type Output = i32;
+
+    #[inline]
+    fn index(&self, axis: Axis) -> &i32 {
+        match axis {
+            Axis::X => &self.x,
+            Axis::Y => &self.y,
+            Axis::Z => &self.z,
+        }
+    }
+}
+
+impl IndexMut<Axis> for IVec3 {
+    #[inline]
+    fn index_mut(&mut self, axis: Axis) -> &mut i32 {
+        match axis {
+            Axis::X => &mut self.x,
+            Axis::Y => &mut self.y,
+            Axis::Z => &mut self.z,
+        }
+    }
+}
+
+impl Add for IVec3 {
+    type Output = IVec3;
+
+    #[inline]
+    fn add(self, rhs: IVec3) -> IVec3 {
+        IVec3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for IVec3 {
+    type Output = IVec3;
+
+    #[inline]
+    fn sub(self, rhs: IVec3) -> IVec3 {
+        IVec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Neg for IVec3 {
+    type Output = IVec3;
+
+    #[inline]
+    fn neg(self) -> IVec3 {
+        IVec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<i32> for IVec3 {
+    type Output = IVec3;
+
+    #[inline]
+    fn mul(self, rhs: i32) -> IVec3 {
+        IVec3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+/// Component-wise multiplication.
+impl Mul for IVec3 {
+    type Output = IVec3;
+
+    #[inline]
+    fn mul(self, rhs: IVec3) -> IVec3 {
+        IVec3::new(self.x * rhs.x, self.y * rhs.y, self.z * rhs.z)
+    }
+}
+
+impl AddAssign for IVec3 {
+    #[inline]
+    fn add_assign(&mut self, rhs: IVec3) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for IVec3 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: IVec3) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign<i32> for IVec3 {
+    #[inline]
+    fn mul_assign(&mut self, rhs: i32) {
+        *self = *self * rhs;
+    }
+}